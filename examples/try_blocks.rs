@@ -0,0 +1,68 @@
+//! Using `TracedResult` as the type of `try` blocks. Run with `cargo +nightly run --example try_blocks`.
+#![feature(try_blocks, try_blocks_heterogeneous)]
+
+use std::{fmt::Display, num::ParseIntError};
+
+use traced_result::{IntoTraced, NoneError, TracedResult};
+
+fn parse(input: &str) -> TracedResult<u32, ParseIntError> {
+    input.parse::<u32>().traced()
+}
+
+#[derive(Debug)]
+enum InputError {
+    Parse(ParseIntError),
+    Missing,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Parse(err) => write!(f, "invalid number: {err}"),
+            InputError::Missing => write!(f, "missing value"),
+        }
+    }
+}
+
+impl From<ParseIntError> for InputError {
+    fn from(err: ParseIntError) -> Self {
+        InputError::Parse(err)
+    }
+}
+
+impl From<NoneError> for InputError {
+    fn from(_: NoneError) -> Self {
+        InputError::Missing
+    }
+}
+
+fn main() {
+    // The block's type is inferred from the `TracedResult`s `?` is used on
+    let total = try { parse("1")? + parse("x")? };
+    let TracedResult::Err(error) = total else {
+        unreachable!("\"x\" is not a number")
+    };
+    // The origin in `parse`, followed by the `?` inside the block
+    assert_eq!(error.trace().len(), 2);
+    println!("homogeneous try block:\n{error}");
+
+    // Naming the type up front also allows `?` on `Result`s and `Option`s, which start a call stack there
+    let from_result =
+        try bikeshed TracedResult<u32, ParseIntError> { parse("1")? + "x".parse::<u32>()? };
+    let TracedResult::Err(error) = from_result else {
+        unreachable!("\"x\" is not a number")
+    };
+    assert_eq!(error.trace().len(), 1);
+    println!("try bikeshed with a Result residual:\n{error}");
+
+    let from_option = try bikeshed TracedResult<u32, InputError> {
+        let missing: Option<u32> = None;
+        parse("1")? + missing?
+    };
+    let TracedResult::Err(error) = from_option else {
+        unreachable!("the value is missing")
+    };
+    assert!(matches!(*error, InputError::Missing));
+    assert_eq!(error.trace().len(), 1);
+    println!("try bikeshed with an Option residual:\n{error}");
+}
@@ -0,0 +1,116 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, ItemFn, ReturnType};
+
+/// Turns a function returning a `TracedResult` into a test.
+/// If the test returns an `Err`, it fails with the error and its full call stack. Panics caused by `unwrap()` or `expect()` on a `TracedResult` also print the call stack (see `traced_result::panic::install_hook`).
+#[proc_macro_attribute]
+pub fn traced_test(_: TokenStream, item: TokenStream) -> TokenStream {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = parse_macro_input!(item as ItemFn);
+
+    let ReturnType::Type(_, output) = &sig.output else {
+        return syn::Error::new_spanned(
+            &sig,
+            "`#[traced_test]` functions must return a `TracedResult`",
+        )
+        .to_compile_error()
+        .into();
+    };
+    let name = &sig.ident;
+
+    quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #name() {
+            ::traced_result::panic::install_hook();
+            let result: #output = (|| -> #output #block)();
+            ::traced_result::__private::finish_test(result);
+        }
+    }
+    .into()
+}
+
+/// Records the path of the annotated function and crate with every frame recorded within it, so call stacks show function names alongside locations.
+/// The function must return a `TracedResult` and cannot be `async`. Frames of methods are labeled with the path of the module, not of the type.
+#[proc_macro_attribute]
+pub fn traced(_: TokenStream, item: TokenStream) -> TokenStream {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = parse_macro_input!(item as ItemFn);
+
+    if let Some(asyncness) = &sig.asyncness {
+        return syn::Error::new_spanned(
+            asyncness,
+            "`#[traced]` does not support `async` functions",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let ReturnType::Type(_, output) = &sig.output else {
+        return syn::Error::new_spanned(&sig, "`#[traced]` functions must return a `TracedResult`")
+            .to_compile_error()
+            .into();
+    };
+    let name = &sig.ident;
+    let first_line = block.brace_token.span.open().unwrap().line() as u32;
+    let last_line = block.brace_token.span.close().unwrap().line() as u32;
+
+    quote! {
+        #(#attrs)*
+        #vis #sig {
+            let result: #output = (move || -> #output #block)();
+            ::traced_result::__private::name_frames(
+                result,
+                ::core::concat!(::core::module_path!(), "::", ::core::stringify!(#name)),
+                (::core::env!("CARGO_PKG_NAME"), ::core::env!("CARGO_PKG_VERSION")),
+                ::core::file!(),
+                #first_line..=#last_line,
+            )
+        }
+    }
+    .into()
+}
+
+/// Attributes the frames recorded inside the annotated function to its caller, to keep utility wrappers that only forward errors out of call stacks.
+/// The function gets `#[track_caller]`, must return a `TracedResult` and cannot be `async`. Frames recorded within its lines are removed from the call stack of a returned error; if the error was created inside the function, the location it was called from becomes its origin instead.
+#[proc_macro_attribute]
+pub fn skip(_: TokenStream, item: TokenStream) -> TokenStream {
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = parse_macro_input!(item as ItemFn);
+
+    if let Some(asyncness) = &sig.asyncness {
+        return syn::Error::new_spanned(asyncness, "`#[skip]` does not support `async` functions")
+            .to_compile_error()
+            .into();
+    }
+    let ReturnType::Type(_, output) = &sig.output else {
+        return syn::Error::new_spanned(&sig, "`#[skip]` functions must return a `TracedResult`")
+            .to_compile_error()
+            .into();
+    };
+    let first_line = block.brace_token.span.open().unwrap().line() as u32;
+    let last_line = block.brace_token.span.close().unwrap().line() as u32;
+
+    quote! {
+        #(#attrs)*
+        #[track_caller]
+        #vis #sig {
+            let caller = ::core::panic::Location::caller();
+            let result: #output = (move || -> #output #block)();
+            ::traced_result::__private::skip_frames(result, caller, ::core::file!(), #first_line..=#last_line)
+        }
+    }
+    .into()
+}
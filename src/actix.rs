@@ -0,0 +1,56 @@
+use std::{
+    fmt::{Debug, Display},
+    sync::OnceLock,
+};
+
+use actix_web::{
+    body::EitherBody, http::StatusCode, HttpRequest, HttpResponse, Responder, ResponseError,
+};
+
+use crate::{HttpStatus, TracedError, TracedResult};
+
+/// A function choosing the status code of the response for a traced error. See `set_status_mapper`.
+pub type StatusMapper = fn(error: &dyn Display) -> StatusCode;
+
+static STATUS_MAPPER: OnceLock<StatusMapper> = OnceLock::new();
+
+/// Choose the status code of error responses using `mapper` instead of the error's `HttpStatus` implementation.
+/// The mapper can only be set once; if one is already installed, `mapper` is returned as an error.
+pub fn set_status_mapper(mapper: StatusMapper) -> Result<(), StatusMapper> {
+    STATUS_MAPPER.set(mapper)
+}
+
+/// Responds with the status returned by the error's `HttpStatus` implementation (or `500` if it is not an error status), unless a mapper was installed using `set_status_mapper`.
+/// In debug builds, the response body contains the error message and its call stack; in release builds, it only contains the status' canonical reason so no internals are leaked to clients.
+impl<E: Debug + Display + HttpStatus> ResponseError for TracedError<E> {
+    fn status_code(&self) -> StatusCode {
+        match STATUS_MAPPER.get() {
+            Some(mapper) => mapper(&self.inner),
+            None => StatusCode::from_u16(self.inner.status())
+                .ok()
+                .filter(|status| status.is_client_error() || status.is_server_error())
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let status = self.status_code();
+        let body = if cfg!(debug_assertions) {
+            self.display_for_report().to_string()
+        } else {
+            status.canonical_reason().unwrap_or_default().to_owned()
+        };
+        HttpResponse::build(status).body(body)
+    }
+}
+
+impl<T: Responder, E: Debug + Display + HttpStatus> Responder for TracedResult<T, E> {
+    type Body = EitherBody<T::Body>;
+
+    fn respond_to(self, req: &HttpRequest) -> HttpResponse<Self::Body> {
+        match self {
+            TracedResult::Ok(ok) => ok.respond_to(req).map_into_left_body(),
+            TracedResult::Err(err) => err.error_response().map_into_right_body(),
+        }
+    }
+}
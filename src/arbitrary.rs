@@ -0,0 +1,156 @@
+use crate::{Frame, FrameKind};
+
+/// Files used for the frames of generated errors.
+const FILES: &[&str] = &[
+    "src/main.rs",
+    "src/lib.rs",
+    "src/config.rs",
+    "src/db/mod.rs",
+    "src/db/query.rs",
+    "src/http/handler.rs",
+];
+
+/// The maximum number of propagation frames added to a generated error's origin frame.
+const MAX_DEPTH: usize = 8;
+
+fn frame(file: usize, line: u32, column: u32, kind: FrameKind) -> Frame {
+    Frame::new(
+        FILES[file % FILES.len()],
+        line % 2000 + 1,
+        column % 120 + 1,
+        kind,
+    )
+}
+
+#[cfg(feature = "proptest")]
+mod proptest {
+    use ::proptest::{collection::vec, prelude::*};
+
+    use super::{frame, MAX_DEPTH};
+    use crate::{Frame, FrameKind, Trace, TracedError, TracedResult};
+
+    fn frame_strategy(kind: FrameKind) -> impl Strategy<Value = Frame> {
+        any::<(usize, u32, u32)>()
+            .prop_map(move |(file, line, column)| frame(file, line, column, kind))
+    }
+
+    impl Arbitrary for Frame {
+        type Parameters = ();
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+            prop_oneof![
+                frame_strategy(FrameKind::Origin),
+                frame_strategy(FrameKind::Propagation),
+            ]
+            .boxed()
+        }
+    }
+
+    /// Generates errors with a synthetic call stack consisting of an origin frame and up to 8 propagation frames.
+    impl<E: Arbitrary + 'static> Arbitrary for TracedError<E> {
+        type Parameters = E::Parameters;
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+            (
+                any_with::<E>(args),
+                frame_strategy(FrameKind::Origin),
+                vec(frame_strategy(FrameKind::Propagation), 0..=MAX_DEPTH),
+            )
+                .prop_map(|(inner, origin, mut frames)| {
+                    frames.insert(0, origin);
+                    TracedError {
+                        trace: Trace::new(frames),
+                        inner,
+                        attachments: Default::default(),
+                    }
+                })
+                .boxed()
+        }
+    }
+
+    impl<T, E> Arbitrary for TracedResult<T, E>
+    where
+        T: Arbitrary + 'static,
+        E: Arbitrary + 'static,
+    {
+        type Parameters = (T::Parameters, E::Parameters);
+        type Strategy = BoxedStrategy<Self>;
+
+        fn arbitrary_with((ok, err): Self::Parameters) -> Self::Strategy {
+            prop_oneof![
+                any_with::<T>(ok).prop_map(TracedResult::Ok),
+                any_with::<TracedError<E>>(err).prop_map(TracedResult::Err),
+            ]
+            .boxed()
+        }
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck {
+    use ::quickcheck::{Arbitrary, Gen};
+
+    use super::{frame, MAX_DEPTH};
+    use crate::{Frame, FrameKind, Trace, TracedError, TracedResult};
+
+    fn arbitrary_frame(g: &mut Gen, kind: FrameKind) -> Frame {
+        frame(
+            usize::arbitrary(g),
+            u32::arbitrary(g),
+            u32::arbitrary(g),
+            kind,
+        )
+    }
+
+    impl Arbitrary for Frame {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let kind = *g
+                .choose(&[FrameKind::Origin, FrameKind::Propagation])
+                .unwrap();
+            arbitrary_frame(g, kind)
+        }
+    }
+
+    /// Generates errors with a synthetic call stack consisting of an origin frame and up to 8 propagation frames.
+    /// Shrinking shrinks the inner error and keeps the call stack.
+    impl<E: Arbitrary> Arbitrary for TracedError<E> {
+        fn arbitrary(g: &mut Gen) -> Self {
+            let depth = usize::arbitrary(g) % (MAX_DEPTH + 1);
+            let mut frames = vec![arbitrary_frame(g, FrameKind::Origin)];
+            frames.extend((0..depth).map(|_| arbitrary_frame(g, FrameKind::Propagation)));
+            TracedError {
+                trace: Trace::new(frames),
+                inner: E::arbitrary(g),
+                attachments: Default::default(),
+            }
+        }
+
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            let (trace, attachments) = (self.trace.clone(), self.attachments.clone());
+            Box::new(self.inner.shrink().map(move |inner| TracedError {
+                trace: trace.clone(),
+                inner,
+                attachments: attachments.clone(),
+            }))
+        }
+    }
+
+    impl<T: Arbitrary, E: Arbitrary> Arbitrary for TracedResult<T, E> {
+        fn arbitrary(g: &mut Gen) -> Self {
+            if bool::arbitrary(g) {
+                TracedResult::Ok(T::arbitrary(g))
+            } else {
+                TracedResult::Err(TracedError::arbitrary(g))
+            }
+        }
+
+        fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+            match self {
+                TracedResult::Ok(ok) => Box::new(ok.shrink().map(TracedResult::Ok)),
+                TracedResult::Err(err) => Box::new(err.shrink().map(TracedResult::Err)),
+            }
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use std::{
+    any::{type_name, Any},
+    fmt::Debug,
+    sync::Arc,
+};
+
+use crate::TracedError;
+
+/// Typed values attached to a `TracedError`. Cloning an error shares its attachments instead of cloning them.
+#[derive(Clone, Default)]
+pub(crate) struct Attachments(Vec<(&'static str, Arc<dyn Any + Send + Sync>)>);
+
+/// Lists the type names of all attachments.
+impl Debug for Attachments {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|(name, _)| name))
+            .finish()
+    }
+}
+
+impl<E> TracedError<E> {
+    /// Attach a value of any type to this error, e.g. a request id or the partial result of an operation, so it can be retrieved by handlers using `get_attachment`.
+    /// Attachments are kept when the error is propagated or converted.
+    pub fn attach<T: Any + Send + Sync>(&mut self, value: T) {
+        self.attachments.0.push((type_name::<T>(), Arc::new(value)));
+    }
+
+    /// Like `attach`, but taking and returning the error by value.
+    pub fn with_attachment<T: Any + Send + Sync>(mut self, value: T) -> Self {
+        self.attach(value);
+        self
+    }
+
+    /// Get the most recently attached value of type `T`, if any.
+    pub fn get_attachment<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.get_attachments().next_back()
+    }
+
+    /// Get all attached values of type `T`, in the order they were attached.
+    pub fn get_attachments<T: Any + Send + Sync>(&self) -> impl DoubleEndedIterator<Item = &T> {
+        self.attachments
+            .0
+            .iter()
+            .filter_map(|(_, value)| value.downcast_ref())
+    }
+}
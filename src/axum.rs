@@ -0,0 +1,49 @@
+use std::{fmt::Display, sync::OnceLock};
+
+use ::axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+
+use crate::{Frame, HttpStatus, TracedError, TracedResult};
+
+/// A function converting a traced error into a response. See `set_response_mapper`.
+pub type ResponseMapper = fn(error: &dyn Display, trace: &[Frame]) -> Response;
+
+static RESPONSE_MAPPER: OnceLock<ResponseMapper> = OnceLock::new();
+
+/// Replace the default conversion of `TracedError`s into responses.
+/// The mapper can only be set once; if one is already installed, `mapper` is returned as an error.
+pub fn set_response_mapper(mapper: ResponseMapper) -> Result<(), ResponseMapper> {
+    RESPONSE_MAPPER.set(mapper)
+}
+
+/// Responds with the status returned by the error's `HttpStatus` implementation, or `500` if it is not an error status.
+/// In debug builds, the body contains the error message and its call stack; in release builds, it only contains the status' canonical reason so no internals are leaked to clients.
+/// This behavior can be customized using `set_response_mapper`.
+impl<E: Display + HttpStatus> IntoResponse for TracedError<E> {
+    fn into_response(self) -> Response {
+        if let Some(mapper) = RESPONSE_MAPPER.get() {
+            return mapper(&self.inner, self.trace());
+        }
+
+        let status = StatusCode::from_u16(self.inner.status())
+            .ok()
+            .filter(|status| status.is_client_error() || status.is_server_error())
+            .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        if cfg!(debug_assertions) {
+            (status, self.display_for_report().to_string()).into_response()
+        } else {
+            (status, status.canonical_reason().unwrap_or_default()).into_response()
+        }
+    }
+}
+
+impl<T: IntoResponse, E: Display + HttpStatus> IntoResponse for TracedResult<T, E> {
+    fn into_response(self) -> Response {
+        match self {
+            TracedResult::Ok(ok) => ok.into_response(),
+            TracedResult::Err(err) => err.into_response(),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use std::{fmt::Display, sync::OnceLock};
+
+/// Information about the binary that produced an error, included in exported reports (e.g. Sentry events) once installed using `set_build_info`.
+/// Usually created using the `build_info!` macro.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BuildInfo {
+    pub crate_name: &'static str,
+    pub crate_version: &'static str,
+    pub git_hash: Option<&'static str>,
+    pub profile: &'static str,
+    pub target_arch: &'static str,
+    pub target_os: &'static str,
+}
+
+/// Formats the build info as `name version (git hash, profile, arch-os)`.
+impl Display for BuildInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} (", self.crate_name, self.crate_version)?;
+        if let Some(git_hash) = self.git_hash {
+            write!(f, "{git_hash}, ")?;
+        }
+        write!(
+            f,
+            "{}, {}-{})",
+            self.profile, self.target_arch, self.target_os
+        )
+    }
+}
+
+static BUILD_INFO: OnceLock<BuildInfo> = OnceLock::new();
+
+/// Install the build info included in exported reports, usually `set_build_info(build_info!())` at the start of `main`.
+/// The build info can only be set once; if it is already installed, `info` is returned as an error.
+pub fn set_build_info(info: BuildInfo) -> Result<(), BuildInfo> {
+    BUILD_INFO.set(info)
+}
+
+/// The build info installed using `set_build_info`, if any.
+pub fn build_info() -> Option<&'static BuildInfo> {
+    BUILD_INFO.get()
+}
@@ -0,0 +1,86 @@
+use std::{fmt::Display, sync::OnceLock};
+
+use crate::{
+    crash,
+    format::{scrub, FormatOptions},
+    Severity, TracedError, TracedResult,
+};
+
+/// The environment variable controlling how much `report_and_exit` prints: `0` (or `quiet`), `1` (or `normal`, the default) or `2` (or `verbose`). See `Verbosity`.
+pub const VERBOSITY_ENV: &str = "TRACED_RESULT_VERBOSITY";
+
+/// How much `report_and_exit` prints about an error.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Verbosity {
+    /// Only the message and help texts, for end users.
+    Quiet,
+    /// The error as formatted by its `Display` implementation, including its call stack.
+    #[default]
+    Normal,
+    /// The call stack in backtrace style, including the function of each frame if known.
+    Verbose,
+}
+
+impl Verbosity {
+    /// The verbosity set using the `VERBOSITY_ENV` environment variable, or `Normal` if it is not set to a valid value.
+    pub fn from_env() -> Self {
+        match std::env::var(VERBOSITY_ENV).as_deref() {
+            Ok("0" | "quiet") => Verbosity::Quiet,
+            Ok("2" | "verbose") => Verbosity::Verbose,
+            _ => Verbosity::Normal,
+        }
+    }
+}
+
+/// A function choosing the exit code for an error based on its code (see `TracedError::with_code`) and severity. See `set_exit_code_mapper`.
+pub type ExitCodeMapper = fn(code: Option<&'static str>, severity: Severity) -> u8;
+
+static EXIT_CODE_MAPPER: OnceLock<ExitCodeMapper> = OnceLock::new();
+
+/// Choose the exit codes used by `report_and_exit`, which are `1` by default.
+/// The mapper can only be set once; if one is already installed, `mapper` is returned as an error.
+pub fn set_exit_code_mapper(mapper: ExitCodeMapper) -> Result<(), ExitCodeMapper> {
+    EXIT_CODE_MAPPER.set(mapper)
+}
+
+/// Print `error` to stderr and exit the process, for CLI tools that handle errors in `main` themselves.
+/// The output follows the verbosity set using `VERBOSITY_ENV` and uses colors as configured by `Config::color`. For errors with the severity `Fatal`, a crash report file is written as well (see `crash::report_crash`). The exit code is `1`, unless another one is chosen by the mapper installed using `set_exit_code_mapper`.
+/// In quiet mode, which is meant for end users, the message is passed through the `Scrubber` installed with `Config::scrubber`.
+/// The process exits without running destructors, so reports still queued on a `reporter::Reporter` are lost; call `Reporter::flush` first if the error should reach its sinks.
+pub fn report_and_exit<E: Display>(error: TracedError<E>) -> ! {
+    let options = FormatOptions::new();
+    match Verbosity::from_env() {
+        Verbosity::Quiet => {
+            eprintln!("error: {}", scrub(&error.rendered_message()));
+            for help in error.help() {
+                eprintln!("help: {help}");
+            }
+        }
+        Verbosity::Normal => eprint!("error: {}", error.display_with(options)),
+        Verbosity::Verbose => {
+            eprint!(
+                "error: {}",
+                error.display_with(options.backtrace_style(true))
+            )
+        }
+    }
+
+    let severity = error.severity().unwrap_or_default();
+    if severity == Severity::Fatal {
+        crash::report_crash(&error);
+    }
+    let code = EXIT_CODE_MAPPER
+        .get()
+        .map_or(1, |mapper| mapper(error.code(), severity));
+    std::process::exit(code.into())
+}
+
+impl<T, E: Display> TracedResult<T, E> {
+    /// Return the `Ok` value, or print the error and exit the process using `report_and_exit`. Like that function, this doesn't flush any `reporter::Reporter`.
+    pub fn unwrap_or_exit(self) -> T {
+        match self {
+            TracedResult::Ok(ok) => ok,
+            TracedResult::Err(err) => report_and_exit(err),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    fmt::Display,
+    panic::Location,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+};
+
+use crate::{FrameKind, MultiTracedError, TracedError, TracedResult};
+
+/// Identifies the worker that sent an error through an `ErrorSender`. Attached to every error sent, see `TracedError::worker`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Worker(Arc<str>);
+
+impl Worker {
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Worker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<E> TracedError<E> {
+    /// The worker that sent this error through an `ErrorSender`, if any.
+    pub fn worker(&self) -> Option<&Worker> {
+        self.get_attachment::<Worker>()
+    }
+}
+
+/// Create a channel through which worker threads or tasks can send their errors to a single `ErrorCollector`, e.g. in batch processing pipelines.
+/// The returned sender belongs to a worker named `main`; use `ErrorSender::for_worker` to create senders for other workers.
+pub fn error_channel<E>() -> (ErrorSender<E>, ErrorCollector<E>) {
+    let (sender, receiver) = mpsc::channel();
+    let sender = ErrorSender {
+        sender,
+        worker: Worker("main".into()),
+    };
+    (sender, ErrorCollector { receiver })
+}
+
+/// The sending half of an `error_channel`, tagging every error it sends with the `Worker` it belongs to.
+#[derive(Debug)]
+pub struct ErrorSender<E> {
+    sender: Sender<TracedError<E>>,
+    worker: Worker,
+}
+
+impl<E> ErrorSender<E> {
+    /// Create a sender for the worker called `name` that sends to the same collector.
+    pub fn for_worker(&self, name: impl Into<Arc<str>>) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            worker: Worker(name.into()),
+        }
+    }
+
+    /// The worker this sender belongs to.
+    pub fn worker(&self) -> &Worker {
+        &self.worker
+    }
+
+    /// Send `error` to the collector, attaching this sender's `Worker`.
+    /// If the collector was dropped, the error is returned.
+    pub fn send(&self, error: TracedError<E>) -> Result<(), TracedError<E>> {
+        self.sender
+            .send(error.with_attachment(self.worker.clone()))
+            .map_err(|err| err.0)
+    }
+}
+
+impl<E> Clone for ErrorSender<E> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+            worker: self.worker.clone(),
+        }
+    }
+}
+
+/// The receiving half of an `error_channel`. Every error it receives gets a frame at the location it was received at.
+#[derive(Debug)]
+pub struct ErrorCollector<E> {
+    receiver: Receiver<TracedError<E>>,
+}
+
+impl<E> ErrorCollector<E> {
+    /// Wait for the next error. Returns `None` once all senders were dropped and every error was received.
+    #[track_caller]
+    pub fn recv(&self) -> Option<TracedError<E>> {
+        let location = Location::caller();
+        self.receiver
+            .recv()
+            .ok()
+            .map(|error| received(error, location))
+    }
+
+    /// Receive the next error if one is available, without waiting.
+    #[track_caller]
+    pub fn try_recv(&self) -> Option<TracedError<E>> {
+        let location = Location::caller();
+        self.receiver
+            .try_recv()
+            .ok()
+            .map(|error| received(error, location))
+    }
+
+    /// Iterate over the received errors until all senders were dropped. The frame added to each error is at the caller location of this method.
+    #[track_caller]
+    pub fn iter(&self) -> impl Iterator<Item = TracedError<E>> + '_ {
+        let location = Location::caller();
+        self.receiver
+            .iter()
+            .map(move |error| received(error, location))
+    }
+
+    /// Wait for all senders to be dropped and collect every error received into a `MultiTracedError`, whose own call stack starts at the caller location of this method.
+    #[track_caller]
+    pub fn finish(self) -> TracedResult<(), MultiTracedError<E>> {
+        let errors: MultiTracedError<E> = self.iter().collect();
+        if errors.is_empty() {
+            TracedResult::Ok(())
+        } else {
+            TracedResult::Err(TracedError::new(errors))
+        }
+    }
+}
+
+/// Add a frame at the location `error` was received at.
+fn received<E>(mut error: TracedError<E>, location: &'static Location<'static>) -> TracedError<E> {
+    error.trace.record_at(location, FrameKind::Propagation);
+    error
+}
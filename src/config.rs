@@ -0,0 +1,186 @@
+use std::{
+    io::IsTerminal,
+    panic::Location,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
+};
+
+use crate::format::{MessageRenderer, Scrubber};
+
+/// Whether formatted errors use ANSI colors. See `Config::color`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ColorChoice {
+    /// Use colors if stderr is a terminal and the `NO_COLOR` environment variable is not set.
+    Auto,
+    Always,
+    #[default]
+    Never,
+}
+
+impl ColorChoice {
+    pub(crate) fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => {
+                std::io::stderr().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+            }
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+static CAPTURE_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Enable or disable recording call stacks at runtime. Enabled by default.
+/// While disabled, new errors start with an empty call stack without allocating, and propagating errors does not record any frames, so tracing can be left compiled into production builds at almost no cost.
+pub fn set_capture_enabled(enabled: bool) {
+    CAPTURE_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether call stacks are currently recorded. See `set_capture_enabled`.
+#[inline(always)]
+pub fn capture_enabled() -> bool {
+    CAPTURE_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Whether a non-origin frame at `location` is added to a call stack that already has `depth` frames: call stacks must be captured, not limited to their origins (see `Config::origin_only`), below `Config::max_depth`, and the location must pass `Config::frame_filter`.
+pub(crate) fn records_frame(location: &Location<'_>, depth: usize) -> bool {
+    if !capture_enabled() {
+        return false;
+    }
+    let config = Config::current();
+    if cfg!(feature = "origin-only") || config.origin_only {
+        return false;
+    }
+    if config.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+        return false;
+    }
+    config.frame_filter.is_none_or(|filter| filter(location))
+}
+
+/// A function deciding whether a frame at the given location is recorded. See `Config::frame_filter`.
+pub type FrameFilter = fn(&Location<'_>) -> bool;
+
+/// The order in which formatted call stacks list their frames. See `Config::frame_order`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FrameOrder {
+    /// Start with the most recently recorded frame and end with the error's origin.
+    #[default]
+    MostRecentFirst,
+    /// Start with the error's origin and end with the most recently recorded frame, like Python tracebacks.
+    MostRecentLast,
+}
+
+/// Process-wide defaults for capturing and formatting call stacks, e.g. `Config::new().max_depth(64).color(ColorChoice::Auto).install()`.
+/// Until a configuration is installed, the defaults of `Config::new()` are used.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub(crate) max_depth: Option<usize>,
+    pub(crate) color: ColorChoice,
+    pub(crate) strip_prefixes: Vec<String>,
+    pub(crate) strict: bool,
+    pub(crate) frame_filter: Option<FrameFilter>,
+    pub(crate) origin_only: bool,
+    pub(crate) frame_order: FrameOrder,
+    pub(crate) trace_combinators: bool,
+    pub(crate) message_renderer: Option<MessageRenderer>,
+    pub(crate) scrubber: Option<Scrubber>,
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+impl Config {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop recording frames once a call stack contains `depth` frames, e.g. to bound the memory used by errors propagated through deep recursion. The origin of an error is always recorded.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Whether errors are formatted using ANSI colors by default. Defaults to `ColorChoice::Never`.
+    pub fn color(mut self, color: ColorChoice) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Remove `prefix` from the start of file paths when formatting errors by default. Can be called multiple times to strip several prefixes.
+    pub fn strip_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.strip_prefixes.push(prefix.into());
+        self
+    }
+
+    /// Enable strict mode, in which dropping an unobserved traced error panics (see the `drop-guard` feature). Equivalent to the `strict` feature or `TRACED_RESULT_STRICT=1`.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Only record frames at locations for which `filter` returns `true`, e.g. to drop frames in generated code or vendored dependencies when they are captured rather than when they are displayed.
+    /// The origin of an error is always recorded.
+    pub fn frame_filter(mut self, filter: FrameFilter) -> Self {
+        self.frame_filter = Some(filter);
+        self
+    }
+
+    /// Only record where errors are created, not where they are propagated or converted. This answers where an error came from at a fraction of the cost of recording full call stacks.
+    /// Equivalent to the `origin-only` feature.
+    pub fn origin_only(mut self, origin_only: bool) -> Self {
+        self.origin_only = origin_only;
+        self
+    }
+
+    /// The order in which formatted call stacks list their frames by default. Defaults to `FrameOrder::MostRecentFirst`.
+    pub fn frame_order(mut self, order: FrameOrder) -> Self {
+        self.frame_order = order;
+        self
+    }
+
+    /// Also record where errors pass through combinators like `TracedResult::map` or `and_then`, so code chaining combinators is traced as thoroughly as code using `?`.
+    /// Equivalent to the `trace-combinators` feature.
+    pub fn trace_combinators(mut self, trace_combinators: bool) -> Self {
+        self.trace_combinators = trace_combinators;
+        self
+    }
+
+    /// Render the messages of errors using `renderer` when they are formatted, e.g. to show localized or customer-friendly messages based on their error code. The call stack is formatted as usual.
+    /// If `renderer` returns `None`, the error's own `Display` output is used. Use `FormatOptions::render_messages(false)` to format the original messages anyway.
+    pub fn message_renderer(mut self, renderer: MessageRenderer) -> Self {
+        self.message_renderer = Some(renderer);
+        self
+    }
+
+    /// Remove sensitive data from errors before they leave the process using `scrubber`, e.g. by replacing tokens and email addresses with placeholders.
+    /// It is applied to the messages and tags exported by every integration (`log`, `sentry`, `otel`, `reporter`, system logs, ...) and by `TracedError::display_for_report`, but not to the `Display` output developers see locally.
+    pub fn scrubber(mut self, scrubber: Scrubber) -> Self {
+        self.scrubber = Some(scrubber);
+        self
+    }
+
+    /// Install this configuration for the entire process.
+    /// The configuration can only be installed once; if one is already installed, `self` is returned as an error.
+    pub fn install(self) -> Result<(), Config> {
+        CONFIG.set(self)
+    }
+
+    /// The installed configuration, or the default one if none was installed.
+    pub fn current() -> &'static Config {
+        static DEFAULT: Config = Config {
+            max_depth: None,
+            color: ColorChoice::Never,
+            strip_prefixes: Vec::new(),
+            strict: false,
+            frame_filter: None,
+            origin_only: false,
+            frame_order: FrameOrder::MostRecentFirst,
+            trace_combinators: false,
+            message_renderer: None,
+            scrubber: None,
+        };
+        CONFIG.get().unwrap_or(&DEFAULT)
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    collections::hash_map::RandomState,
+    fmt::{Display, Write},
+    fs::OpenOptions,
+    hash::BuildHasher,
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    build_info::build_info,
+    format::{scrub, FormatOptions},
+    Frame, TracedError,
+};
+
+/// The contents of a crash report file. See `write_crash_report`.
+pub(crate) struct CrashReport<'a> {
+    pub(crate) message: String,
+    pub(crate) location: Option<String>,
+    pub(crate) details: Vec<(&'static str, String)>,
+    pub(crate) frames: &'a [Frame],
+}
+
+impl CrashReport<'_> {
+    /// Write this report to a new file in the temporary directory, returning its path.
+    /// The file name ends with a random suffix and the file must not exist yet, so other users of the temporary directory can neither predict it nor redirect the report using a symlink.
+    pub(crate) fn write(&self) -> io::Result<PathBuf> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let mut attempts = 0;
+        loop {
+            let suffix = RandomState::new().hash_one(attempts);
+            let path = std::env::temp_dir().join(format!(
+                "{}-crash-{}-{timestamp}-{suffix:016x}.txt",
+                app_name(),
+                std::process::id()
+            ));
+            let mut options = OpenOptions::new();
+            options.write(true).create_new(true);
+            // Only the user running the application should be able to read the report
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            match options.open(&path) {
+                Ok(mut file) => {
+                    file.write_all(self.to_string().as_bytes())?;
+                    return Ok(path);
+                }
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists && attempts < 8 => {
+                    attempts += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+impl Display for CrashReport<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match build_info() {
+            Some(info) => writeln!(f, "Crash report for {info}")?,
+            None => writeln!(f, "Crash report for {}", app_name())?,
+        }
+        writeln!(f, "\nMessage: {}", self.message)?;
+        if let Some(location) = &self.location {
+            writeln!(f, "Location: {location}")?;
+        }
+        for (name, value) in &self.details {
+            writeln!(f, "{name}: {value}")?;
+        }
+
+        if !self.frames.is_empty() {
+            writeln!(f, "\nCall stack:")?;
+            let options = FormatOptions::new().color(false);
+            let mut frames = String::new();
+            for frame in options.ordered(self.frames) {
+                options.write_frame(&mut frames, frame)?;
+            }
+            f.write_str(&frames)?;
+        }
+
+        writeln!(f, "\nEnvironment:")?;
+        writeln!(
+            f,
+            "Operating system: {} ({})",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )?;
+        if let Ok(executable) = std::env::current_exe() {
+            writeln!(
+                f,
+                "Executable: {}",
+                scrub(&executable.display().to_string())
+            )?;
+        }
+        if let Ok(directory) = std::env::current_dir() {
+            writeln!(
+                f,
+                "Working directory: {}",
+                scrub(&directory.display().to_string())
+            )?;
+        }
+        for name in ENVIRONMENT_VARIABLES {
+            if let Ok(value) = std::env::var(name) {
+                writeln!(f, "{name}={}", scrub(&value))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The environment variables included in crash reports. Other variables are left out, since they often contain credentials.
+const ENVIRONMENT_VARIABLES: [&str; 5] = [
+    "LANG",
+    "TERM",
+    "RUST_BACKTRACE",
+    "RUST_LOG",
+    crate::cli::VERBOSITY_ENV,
+];
+
+/// The name of the application, taken from the installed build info or the executable.
+fn app_name() -> String {
+    if let Some(info) = build_info() {
+        return info.crate_name.to_owned();
+    }
+    std::env::current_exe()
+        .ok()
+        .as_deref()
+        .and_then(Path::file_stem)
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "application".to_owned())
+}
+
+/// Tell the user that the application crashed and where the crash report was written to, on stderr.
+pub(crate) fn announce(path: io::Result<PathBuf>) {
+    let mut message = format!(
+        "\nWell, this is embarrassing.\n\n{} had a problem and crashed.",
+        app_name()
+    );
+    match path {
+        Ok(path) => {
+            let _ = write!(
+                message,
+                " A report has been written to \"{}\".\nPlease attach it when reporting this issue.",
+                path.display()
+            );
+        }
+        Err(err) => {
+            let _ = write!(message, " The crash report could not be written: {err}");
+        }
+    }
+    eprintln!("{message}");
+}
+
+/// Write a crash report for `error` to a new file in the temporary directory, and return its path.
+/// The report contains the error's message, code, severity and tags, its call stack, the build info installed using `build_info::set_build_info` and a description of the environment: the operating system, executable, working directory and a few environment variables relevant to debugging (`LANG`, `TERM`, `RUST_BACKTRACE`, `RUST_LOG` and `cli::VERBOSITY_ENV`). The message, tags, paths and environment variables are passed through the `Scrubber` installed with `Config::scrubber`, since users are asked to share the report.
+pub fn write_crash_report<E: Display>(error: &TracedError<E>) -> io::Result<PathBuf> {
+    let mut details = Vec::new();
+    if let Some(code) = error.code() {
+        details.push(("Code", code.to_owned()));
+    }
+    details.push(("Severity", error.severity().unwrap_or_default().to_string()));
+    for (key, value) in error.tags() {
+        details.push(("Tag", format!("{key}={}", scrub(value))));
+    }
+    CrashReport {
+        message: error.scrubbed_message(),
+        location: None,
+        details,
+        frames: error.trace(),
+    }
+    .write()
+}
+
+/// Write a crash report for `error` (see `write_crash_report`) and tell the user where to find it on stderr, like CLI tools usually do when they crash.
+/// `cli::report_and_exit` does this for errors with the severity `Fatal`.
+pub fn report_crash<E: Display>(error: &TracedError<E>) {
+    announce(write_crash_report(error));
+}
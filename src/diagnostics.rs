@@ -0,0 +1,74 @@
+use std::{collections::HashSet, fmt::Display};
+
+use crate::{MultiTracedError, TracedError, TracedResult};
+
+/// Collects the errors of a pass that should report as many errors as possible instead of stopping at the first one (e.g. a parser or validator), and reports them all at once in the end.
+#[derive(Debug, Clone)]
+pub struct DiagnosticsSink<E> {
+    errors: Vec<TracedError<E>>,
+}
+
+impl<E> DiagnosticsSink<E> {
+    pub fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    pub fn push(&mut self, error: TracedError<E>) {
+        self.errors.push(error);
+    }
+
+    /// Collect the error of `result`, if any, and return its `Ok` value otherwise.
+    pub fn record<T>(&mut self, result: TracedResult<T, E>) -> Option<T> {
+        match result {
+            TracedResult::Ok(ok) => Some(ok),
+            TracedResult::Err(err) => {
+                self.push(err);
+                None
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+impl<E: Display> DiagnosticsSink<E> {
+    /// Get all collected errors, sorted by the location where they were created.
+    /// Errors created at the same location with the same message are only reported once.
+    pub fn finish(self) -> MultiTracedError<E> {
+        let mut errors = self.errors;
+        errors.sort_by_key(|error| {
+            error
+                .trace()
+                .first()
+                .map(|origin| (origin.file(), origin.line(), origin.column()))
+        });
+
+        let mut seen = HashSet::new();
+        errors
+            .retain(|error| seen.insert((error.trace().first().copied(), error.inner.to_string())));
+        errors.into()
+    }
+
+    /// Returns `Ok(())` if no errors were collected, or all errors as returned by `finish` otherwise.
+    pub fn into_result(self) -> Result<(), MultiTracedError<E>> {
+        self.finish().into_result()
+    }
+}
+
+impl<E> Default for DiagnosticsSink<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> Extend<TracedError<E>> for DiagnosticsSink<E> {
+    fn extend<I: IntoIterator<Item = TracedError<E>>>(&mut self, iter: I) {
+        self.errors.extend(iter);
+    }
+}
@@ -0,0 +1,61 @@
+use std::{error::Error, panic::Location};
+
+use ::error_stack::Report;
+
+use crate::{Frame, FrameKind, Trace, TracedError};
+
+/// Converts the error into a report whose context is the inner error, with every frame of the call stack attached, origin first.
+/// Attachments and other metadata of the `TracedError` are not kept.
+impl<E: Error + Send + Sync + 'static> From<TracedError<E>> for Report<E> {
+    #[track_caller]
+    fn from(error: TracedError<E>) -> Self {
+        let (inner, frames) = error.split();
+        frames
+            .into_iter()
+            .fold(Report::new(inner), |report, frame| report.attach(frame))
+    }
+}
+
+impl<C> TracedError<Report<C>> {
+    /// Wrap a report into a `TracedError`, continuing the call stack recorded in the report, followed by a frame at the caller location of this method.
+    /// If frames are attached to the report (e.g. by converting a `TracedError` into a `Report`), they are used as they are. Otherwise, the locations recorded by `error-stack` itself become frames, the oldest one being the origin.
+    #[track_caller]
+    pub fn from_report(report: Report<C>) -> Self {
+        let mut frames: Vec<Frame> = report
+            .frames()
+            .filter_map(|frame| frame.downcast_ref::<Frame>().copied())
+            .collect();
+        if frames.is_empty() {
+            frames = report
+                .frames()
+                .filter_map(|frame| frame.downcast_ref::<Location<'static>>())
+                .map(|location| {
+                    Frame::new(
+                        location.file(),
+                        location.line(),
+                        location.column(),
+                        FrameKind::Propagation,
+                    )
+                })
+                .collect();
+            if let Some(origin) = frames.last_mut() {
+                *origin = Frame::new(
+                    origin.file(),
+                    origin.line(),
+                    origin.column(),
+                    FrameKind::Origin,
+                );
+            }
+        }
+        // Reports list their most recent frames first
+        frames.reverse();
+        let mut trace = Trace::new(frames);
+        trace.record(FrameKind::Propagation);
+
+        TracedError {
+            trace,
+            inner: report,
+            attachments: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    ffi::{c_char, CString},
+    fmt::Display,
+    ptr,
+};
+
+use crate::{Frame, TracedError};
+
+/// An opaque, type-erased traced error for use from C. Frames are indexed in the order they were recorded, i.e. index `0` is where the error was created.
+pub struct TracedErrorHandle {
+    message: CString,
+    files: Vec<CString>,
+    trace: Vec<Frame>,
+}
+
+fn c_string(value: &str) -> CString {
+    // Interior NUL bytes would truncate the string on the C side anyway
+    CString::new(value.replace('\0', "")).unwrap_or_default()
+}
+
+impl<E: Display> TracedError<E> {
+    /// Convert this error into a heap-allocated handle that can be passed to C code.
+    /// The handle must be released using `traced_error_free`. The message is passed through the `Scrubber` installed with `Config::scrubber`.
+    pub fn into_ffi_handle(self) -> *mut TracedErrorHandle {
+        let handle = TracedErrorHandle {
+            message: c_string(&self.scrubbed_message()),
+            files: self
+                .trace
+                .iter()
+                .map(|location| c_string(location.file()))
+                .collect(),
+            trace: self.trace.into_frames(),
+        };
+        Box::into_raw(Box::new(handle))
+    }
+}
+
+/// Get the number of frames in the error's call stack, or `0` if `handle` is null.
+///
+/// # Safety
+/// `handle` must be null or a live handle obtained from `TracedError::into_ffi_handle`.
+#[no_mangle]
+pub unsafe extern "C" fn traced_error_frame_count(handle: *const TracedErrorHandle) -> usize {
+    handle.as_ref().map_or(0, |handle| handle.trace.len())
+}
+
+/// Get the file of the frame at `index` as a NUL-terminated string, or null if `handle` is null or `index` is out of bounds.
+/// The string is owned by the handle and lives as long as it does.
+///
+/// # Safety
+/// `handle` must be null or a live handle obtained from `TracedError::into_ffi_handle`.
+#[no_mangle]
+pub unsafe extern "C" fn traced_error_frame_file(
+    handle: *const TracedErrorHandle,
+    index: usize,
+) -> *const c_char {
+    handle
+        .as_ref()
+        .and_then(|handle| handle.files.get(index))
+        .map_or(ptr::null(), |file| file.as_ptr())
+}
+
+/// Get the line of the frame at `index`, or `0` if `handle` is null or `index` is out of bounds.
+///
+/// # Safety
+/// `handle` must be null or a live handle obtained from `TracedError::into_ffi_handle`.
+#[no_mangle]
+pub unsafe extern "C" fn traced_error_frame_line(
+    handle: *const TracedErrorHandle,
+    index: usize,
+) -> u32 {
+    handle
+        .as_ref()
+        .and_then(|handle| handle.trace.get(index))
+        .map_or(0, |location| location.line())
+}
+
+/// Get the column of the frame at `index`, or `0` if `handle` is null or `index` is out of bounds.
+///
+/// # Safety
+/// `handle` must be null or a live handle obtained from `TracedError::into_ffi_handle`.
+#[no_mangle]
+pub unsafe extern "C" fn traced_error_frame_col(
+    handle: *const TracedErrorHandle,
+    index: usize,
+) -> u32 {
+    handle
+        .as_ref()
+        .and_then(|handle| handle.trace.get(index))
+        .map_or(0, |location| location.column())
+}
+
+/// Get the error's message as a NUL-terminated string, or null if `handle` is null.
+/// The string is owned by the handle and lives as long as it does.
+///
+/// # Safety
+/// `handle` must be null or a live handle obtained from `TracedError::into_ffi_handle`.
+#[no_mangle]
+pub unsafe extern "C" fn traced_error_message(handle: *const TracedErrorHandle) -> *const c_char {
+    handle
+        .as_ref()
+        .map_or(ptr::null(), |handle| handle.message.as_ptr())
+}
+
+/// Release a handle. Passing null is a no-op.
+///
+/// # Safety
+/// `handle` must be null or a live handle obtained from `TracedError::into_ffi_handle`. It must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn traced_error_free(handle: *mut TracedErrorHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
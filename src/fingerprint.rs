@@ -0,0 +1,97 @@
+use std::any::type_name;
+
+use crate::{Frame, TracedError};
+
+/// Options controlling which parts of a call stack contribute to `TracedError::fingerprint_with`.
+/// By default, only the origin of an error is used, including its line and column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FingerprintOptions {
+    frames: Option<usize>,
+    positions: bool,
+}
+
+impl Default for FingerprintOptions {
+    fn default() -> Self {
+        Self {
+            frames: Some(1),
+            positions: true,
+        }
+    }
+}
+
+impl FingerprintOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the first `count` frames of the call stack, starting at the origin.
+    pub fn frames(mut self, count: usize) -> Self {
+        self.frames = Some(count);
+        self
+    }
+
+    /// Use the entire call stack, so errors taking different paths to the same handler get different fingerprints.
+    pub fn all_frames(mut self) -> Self {
+        self.frames = None;
+        self
+    }
+
+    /// Whether line and column numbers are used. Disabling this keeps fingerprints the same when unrelated code in the same file moves.
+    pub fn positions(mut self, positions: bool) -> Self {
+        self.positions = positions;
+        self
+    }
+}
+
+impl<E> TracedError<E> {
+    /// A hash of the error type and the location where this error was created, for grouping recurring errors regardless of their messages.
+    /// Fingerprints are stable across runs and machines as long as the relevant code does not move. The error type is identified by `std::any::type_name`, whose output is not guaranteed to be stable, so fingerprints may change when building with a different compiler version. See `fingerprint_with` to use more of the call stack.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint_with(FingerprintOptions::default())
+    }
+
+    /// Like `fingerprint`, but using custom options.
+    pub fn fingerprint_with(&self, options: FingerprintOptions) -> u64 {
+        let frames = self.trace();
+        let frames = &frames[..options.frames.unwrap_or(usize::MAX).min(frames.len())];
+
+        let mut hasher = Fnv1a::new();
+        hasher.write(type_name::<E>().as_bytes());
+        for frame in frames {
+            hash_frame(&mut hasher, frame, options.positions);
+        }
+        hasher.finish()
+    }
+}
+
+fn hash_frame(hasher: &mut Fnv1a, frame: &Frame, positions: bool) {
+    hasher.write(&[0xff]);
+    for byte in frame.file().bytes() {
+        // Paths recorded on Windows use `\` as separator
+        hasher.write(&[if byte == b'\\' { b'/' } else { byte }]);
+    }
+    if positions {
+        hasher.write(&frame.line().to_le_bytes());
+        hasher.write(&frame.column().to_le_bytes());
+    }
+}
+
+/// The 64-bit FNV-1a hash. Unlike `std`'s `DefaultHasher`, its output is guaranteed not to change between Rust versions.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
@@ -0,0 +1,396 @@
+use std::{
+    borrow::Cow,
+    fmt::{Display, Formatter, Result, Write},
+};
+
+use crate::{Config, Frame, FrameKind, FrameOrder, TracedError};
+
+/// A function rendering the message of an error for display, e.g. in the user's language. Returns `None` to use the error's own `Display` output. See `Config::message_renderer`.
+pub type MessageRenderer = fn(&MessageContext<'_>) -> Option<String>;
+
+/// A function removing sensitive data like tokens, email addresses or paths from text about to leave the process, e.g. the message of a reported error. See `Config::scrubber`.
+pub type Scrubber = fn(&str) -> Cow<'_, str>;
+
+/// The information about an error available to a `MessageRenderer`.
+#[derive(Debug, Clone, Copy)]
+pub struct MessageContext<'a> {
+    message: &'a str,
+    code: Option<&'static str>,
+    type_name: &'static str,
+}
+
+impl MessageContext<'_> {
+    /// The `Display` output of the error.
+    pub fn message(&self) -> &str {
+        self.message
+    }
+
+    /// The error's code, if one was set using `TracedError::with_code`.
+    pub fn code(&self) -> Option<&'static str> {
+        self.code
+    }
+
+    /// The name of the error's type, as returned by `std::any::type_name`.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+}
+
+/// Options controlling how a `TracedError` is formatted by `TracedError::display_with`.
+/// The default options produce the same output as the error's `Display` implementation, and are taken from the installed `Config`.
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    redact_positions: bool,
+    normalize_paths: bool,
+    color: bool,
+    strip_prefixes: Vec<String>,
+    backtrace_style: bool,
+    frame_order: FrameOrder,
+    columns: bool,
+    summary: bool,
+    render_messages: bool,
+    scrub: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        let config = Config::current();
+        Self {
+            redact_positions: false,
+            normalize_paths: false,
+            color: config.color.enabled(),
+            strip_prefixes: config.strip_prefixes.clone(),
+            backtrace_style: false,
+            frame_order: config.frame_order,
+            columns: !cfg!(feature = "no-columns"),
+            summary: false,
+            render_messages: true,
+            scrub: false,
+        }
+    }
+}
+
+impl FormatOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Options producing output that only changes when the propagation path of an error changes, for use in snapshot tests.
+    /// This redacts line and column numbers, normalizes paths and disables colors.
+    pub fn stable() -> Self {
+        Self::new()
+            .redact_positions(true)
+            .normalize_paths(true)
+            .color(false)
+    }
+
+    /// Replace line and column numbers with `_`.
+    pub fn redact_positions(mut self, redact: bool) -> Self {
+        self.redact_positions = redact;
+        self
+    }
+
+    /// Use `/` as path separator, and make absolute paths relative to the `CARGO_MANIFEST_DIR` of the running test (if set) or to the cargo registry, so they are the same on every machine.
+    pub fn normalize_paths(mut self, normalize: bool) -> Self {
+        self.normalize_paths = normalize;
+        self
+    }
+
+    /// Highlight messages and paths using ANSI colors.
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Remove `prefix` from the start of file paths. Can be called multiple times to strip several prefixes.
+    pub fn strip_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.strip_prefixes.push(prefix.into());
+        self
+    }
+
+    /// Include column numbers. Enabled by default, unless columns are not recorded at all because the `no-columns` feature is enabled.
+    pub fn columns(mut self, columns: bool) -> Self {
+        self.columns = columns;
+        self
+    }
+
+    /// The order in which frames are listed. Backtrace-style output always starts at the origin.
+    pub fn frame_order(mut self, order: FrameOrder) -> Self {
+        self.frame_order = order;
+        self
+    }
+
+    /// Pass messages and tag values through the `Scrubber` installed with `Config::scrubber`, if any. Disabled by default; `TracedError::display_for_report` enables it.
+    pub fn scrub(mut self, scrub: bool) -> Self {
+        self.scrub = scrub;
+        self
+    }
+
+    /// Render error messages using the `MessageRenderer` installed with `Config::message_renderer`, if any. Enabled by default; disable it to show developers the original messages.
+    pub fn render_messages(mut self, render: bool) -> Self {
+        self.render_messages = render;
+        self
+    }
+
+    /// Instead of listing every frame, list each distinct file the error passed through once, along with the number of frames recorded in it, for a quick overview of long call stacks.
+    /// Files are listed in the order of their first frame in the configured frame order.
+    pub fn summary(mut self, summary: bool) -> Self {
+        self.summary = summary;
+        self
+    }
+
+    /// Write one line per distinct file in `trace`, with the number of frames in it.
+    fn write_summary(&self, f: &mut dyn Write, trace: &[Frame]) -> Result {
+        let mut files: Vec<(&str, usize)> = Vec::new();
+        for frame in self.ordered(trace) {
+            match files.iter_mut().find(|(file, _)| *file == frame.file()) {
+                Some((_, count)) => *count += 1,
+                None => files.push((frame.file(), 1)),
+            }
+        }
+        for (file, count) in files {
+            let frames = if count == 1 { "frame" } else { "frames" };
+            writeln!(f, "In {} ({count} {frames})", self.styled_file(file))?;
+        }
+        Ok(())
+    }
+
+    /// The frames of `trace` in the configured order.
+    pub(crate) fn ordered<'a>(
+        &self,
+        trace: &'a [Frame],
+    ) -> Box<dyn Iterator<Item = &'a Frame> + 'a> {
+        match self.frame_order {
+            FrameOrder::MostRecentFirst => Box::new(trace.iter().rev()),
+            FrameOrder::MostRecentLast => Box::new(trace.iter()),
+        }
+    }
+
+    /// Mimic the layout of the backtraces printed by `RUST_BACKTRACE=1`, with numbered frames starting at the origin, so tools parsing backtraces can handle the output.
+    /// Frames are labeled with their function or module path, if known.
+    pub fn backtrace_style(mut self, backtrace_style: bool) -> Self {
+        self.backtrace_style = backtrace_style;
+        self
+    }
+
+    /// Write the two lines describing `frame` in backtrace style.
+    fn write_backtrace_frame(&self, f: &mut dyn Write, index: usize, frame: &Frame) -> Result {
+        let symbol = frame
+            .function()
+            .or(frame.module_path())
+            .unwrap_or("<unknown>");
+        writeln!(f, "{index:>4}: {symbol}")?;
+
+        let file = self.styled_file(frame.file());
+        writeln!(f, "             at {file}:{}", self.position(frame))
+    }
+
+    /// Write a single line describing `frame`.
+    pub(crate) fn write_frame(&self, f: &mut dyn Write, frame: &Frame) -> Result {
+        match frame.kind() {
+            FrameKind::Conversion { from, to } => write!(f, "Converted from {from} into {to} at ")?,
+            FrameKind::Merge => write!(f, "Merged at ")?,
+            FrameKind::Retry { attempt } => write!(f, "Attempt #{attempt} failed at ")?,
+            FrameKind::ProcessBoundary => write!(f, "Received from another process at ")?,
+            _ => write!(f, "At ")?,
+        }
+
+        let file = self.styled_file(frame.file());
+        write!(f, "({}) in {file}", self.position(frame))?;
+
+        if let Some(path) = frame.function().or(frame.module_path()) {
+            write!(f, " ({path})")?;
+        }
+        match (frame.crate_name(), frame.crate_version()) {
+            (Some(name), Some(version)) => writeln!(f, " [{name} {version}]"),
+            (Some(name), None) => writeln!(f, " [{name}]"),
+            _ => writeln!(f),
+        }
+    }
+
+    /// The line and column of `frame`, separated by `:`.
+    fn position(&self, frame: &Frame) -> String {
+        match (self.redact_positions, self.columns) {
+            (true, true) => "_:_".to_owned(),
+            (true, false) => "_".to_owned(),
+            (false, true) => format!("{}:{}", frame.line(), frame.column()),
+            (false, false) => frame.line().to_string(),
+        }
+    }
+
+    /// The normalized file, highlighted if colors are enabled.
+    fn styled_file<'a>(&self, file: &'a str) -> Cow<'a, str> {
+        let file = self.file(file);
+        if self.color {
+            Cow::Owned(format!("\x1b[36m{file}\x1b[0m"))
+        } else {
+            file
+        }
+    }
+
+    fn file<'a>(&self, mut file: &'a str) -> Cow<'a, str> {
+        if let Some(stripped) = self
+            .strip_prefixes
+            .iter()
+            .find_map(|prefix| file.strip_prefix(prefix.as_str()))
+        {
+            file = stripped.trim_start_matches(['/', '\\']);
+        }
+        if !self.normalize_paths {
+            return Cow::Borrowed(file);
+        }
+
+        let mut file = file.replace('\\', "/");
+        if let Ok(manifest_dir) = std::env::var("CARGO_MANIFEST_DIR") {
+            let manifest_dir = manifest_dir.replace('\\', "/");
+            if let Some(relative) = file.strip_prefix(&manifest_dir) {
+                file = relative.trim_start_matches('/').to_owned();
+            }
+        }
+        // Registry paths look like `~/.cargo/registry/src/<index>/<crate>-<version>/src/lib.rs`
+        if let Some((_, registry)) = file.split_once("/.cargo/registry/src/") {
+            if let Some((_, crate_path)) = registry.split_once('/') {
+                file = crate_path.to_owned();
+            }
+        }
+        Cow::Owned(file)
+    }
+}
+
+/// A `TracedError` formatted using custom `FormatOptions`. See `TracedError::display_with`.
+pub struct Formatted<'a, E> {
+    error: &'a TracedError<E>,
+    trace: Cow<'a, [Frame]>,
+    options: FormatOptions,
+}
+
+impl<'a, E> Formatted<'a, E> {
+    /// Format `error` as if its call stack was `trace`.
+    pub(crate) fn with_trace(
+        error: &'a TracedError<E>,
+        trace: Vec<Frame>,
+        options: FormatOptions,
+    ) -> Self {
+        Self {
+            error,
+            trace: Cow::Owned(trace),
+            options,
+        }
+    }
+}
+
+impl<E> TracedError<E> {
+    /// Format this error using the specified options instead of the defaults used by its `Display` implementation.
+    pub fn display_with(&self, options: FormatOptions) -> Formatted<'_, E> {
+        Formatted {
+            error: self,
+            trace: Cow::Borrowed(self.trace()),
+            options,
+        }
+    }
+}
+
+/// Apply the `Scrubber` installed with `Config::scrubber` to `text`, if any.
+pub(crate) fn scrub(text: &str) -> Cow<'_, str> {
+    match Config::current().scrubber {
+        Some(scrubber) => scrubber(text),
+        None => Cow::Borrowed(text),
+    }
+}
+
+impl<E> TracedError<E> {
+    /// Format this error for output leaving the process, like reports and logs. Unlike the `Display` implementation, this passes the message and tags through the `Scrubber` installed with `Config::scrubber`, and never uses colors.
+    pub fn display_for_report(&self) -> Formatted<'_, E> {
+        self.display_with(FormatOptions::new().scrub(true).color(false))
+    }
+}
+
+impl<E: Display> TracedError<E> {
+    /// The `Display` output of this error (without its call stack), passed through the `Scrubber` installed with `Config::scrubber`. Integrations exporting errors use this instead of the original message.
+    pub fn scrubbed_message(&self) -> String {
+        let message = self.inner.to_string();
+        match scrub(&message) {
+            Cow::Borrowed(_) => message,
+            Cow::Owned(scrubbed) => scrubbed,
+        }
+    }
+
+    /// The message of this error as rendered by the `MessageRenderer` installed with `Config::message_renderer`, or its `Display` output if there is none.
+    /// Unlike the `Display` implementation of `TracedError`, this does not include the call stack, so it can be shown to users.
+    pub fn rendered_message(&self) -> String {
+        let message = self.inner.to_string();
+        Config::current()
+            .message_renderer
+            .and_then(|renderer| {
+                renderer(&MessageContext {
+                    message: &message,
+                    code: self.code(),
+                    type_name: std::any::type_name::<E>(),
+                })
+            })
+            .unwrap_or(message)
+    }
+}
+
+impl<E: Display> Display for Formatted<'_, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if let Some(code) = self.error.code() {
+            write!(f, "[{code}] ")?;
+        }
+        let mut message = if self.options.render_messages {
+            self.error.rendered_message()
+        } else {
+            self.error.inner.to_string()
+        };
+        if self.options.scrub {
+            message = scrub(&message).into_owned();
+        }
+        if self.options.color {
+            write!(f, "\x1b[1;31m{message}\x1b[0m")?;
+        } else {
+            f.write_str(&message)?;
+        }
+        writeln!(f)?;
+
+        if self.options.summary {
+            self.options.write_summary(f, &self.trace)?;
+        } else if self.options.backtrace_style {
+            writeln!(f, "stack backtrace:")?;
+            for (index, frame) in self.trace.iter().enumerate() {
+                self.options.write_backtrace_frame(f, index, frame)?;
+            }
+        } else {
+            for frame in self.options.ordered(&self.trace) {
+                self.options.write_frame(f, frame)?;
+            }
+        }
+        let mut tags = self.error.tags().peekable();
+        if tags.peek().is_some() {
+            f.write_str("tags:")?;
+            for (key, value) in tags {
+                if self.options.scrub {
+                    write!(f, " {key}={}", scrub(value))?;
+                } else {
+                    write!(f, " {key}={value}")?;
+                }
+            }
+            writeln!(f)?;
+        }
+        for help in self.error.help() {
+            if self.options.color {
+                writeln!(f, "\x1b[1;32mhelp\x1b[0m: {help}")?;
+            } else {
+                writeln!(f, "help: {help}")?;
+            }
+        }
+        if let Some(url) = self.error.url() {
+            if self.options.color {
+                // OSC 8 hyperlink, shown as plain text by terminals that don't support it
+                writeln!(f, "see: \x1b]8;;{url}\x1b\\{url}\x1b]8;;\x1b\\")?;
+            } else {
+                writeln!(f, "see: {url}")?;
+            }
+        }
+        Ok(())
+    }
+}
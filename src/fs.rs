@@ -0,0 +1,118 @@
+use std::{
+    fmt::Display,
+    fs::{File, Metadata, ReadDir},
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::{TracedError, TracedResult};
+
+/// The payload of the `io::Error`s returned by this module, adding the affected path to the original error.
+/// The original error's `ErrorKind` is preserved, and the path can be retrieved by downcasting the `io::Error`'s inner error (see `io::Error::get_ref`) to `PathError`.
+#[derive(Debug)]
+pub struct PathError {
+    path: PathBuf,
+    source: io::Error,
+}
+
+impl PathError {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.source)
+    }
+}
+
+impl std::error::Error for PathError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[track_caller]
+fn with_path<T>(result: io::Result<T>, path: &Path) -> TracedResult<T, io::Error> {
+    match result {
+        Ok(ok) => TracedResult::Ok(ok),
+        Err(source) => TracedResult::Err(TracedError::new(io::Error::new(
+            source.kind(),
+            PathError {
+                path: path.to_owned(),
+                source,
+            },
+        ))),
+    }
+}
+
+/// Traced equivalent of `std::fs::read()`
+#[track_caller]
+pub fn read<P: AsRef<Path>>(path: P) -> TracedResult<Vec<u8>, io::Error> {
+    let path = path.as_ref();
+    with_path(std::fs::read(path), path)
+}
+
+/// Traced equivalent of `std::fs::read_to_string()`
+#[track_caller]
+pub fn read_to_string<P: AsRef<Path>>(path: P) -> TracedResult<String, io::Error> {
+    let path = path.as_ref();
+    with_path(std::fs::read_to_string(path), path)
+}
+
+/// Traced equivalent of `std::fs::write()`
+#[track_caller]
+pub fn write<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> TracedResult<(), io::Error> {
+    let path = path.as_ref();
+    with_path(std::fs::write(path, contents), path)
+}
+
+/// Traced equivalent of `std::fs::File::open()`
+#[track_caller]
+pub fn open<P: AsRef<Path>>(path: P) -> TracedResult<File, io::Error> {
+    let path = path.as_ref();
+    with_path(File::open(path), path)
+}
+
+/// Traced equivalent of `std::fs::File::create()`
+#[track_caller]
+pub fn create<P: AsRef<Path>>(path: P) -> TracedResult<File, io::Error> {
+    let path = path.as_ref();
+    with_path(File::create(path), path)
+}
+
+/// Traced equivalent of `std::fs::create_dir_all()`
+#[track_caller]
+pub fn create_dir_all<P: AsRef<Path>>(path: P) -> TracedResult<(), io::Error> {
+    let path = path.as_ref();
+    with_path(std::fs::create_dir_all(path), path)
+}
+
+/// Traced equivalent of `std::fs::read_dir()`
+#[track_caller]
+pub fn read_dir<P: AsRef<Path>>(path: P) -> TracedResult<ReadDir, io::Error> {
+    let path = path.as_ref();
+    with_path(std::fs::read_dir(path), path)
+}
+
+/// Traced equivalent of `std::fs::metadata()`
+#[track_caller]
+pub fn metadata<P: AsRef<Path>>(path: P) -> TracedResult<Metadata, io::Error> {
+    let path = path.as_ref();
+    with_path(std::fs::metadata(path), path)
+}
+
+/// Traced equivalent of `std::fs::remove_file()`
+#[track_caller]
+pub fn remove_file<P: AsRef<Path>>(path: P) -> TracedResult<(), io::Error> {
+    let path = path.as_ref();
+    with_path(std::fs::remove_file(path), path)
+}
+
+/// Traced equivalent of `std::fs::remove_dir_all()`
+#[track_caller]
+pub fn remove_dir_all<P: AsRef<Path>>(path: P) -> TracedResult<(), io::Error> {
+    let path = path.as_ref();
+    with_path(std::fs::remove_dir_all(path), path)
+}
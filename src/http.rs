@@ -0,0 +1,55 @@
+use std::{convert::Infallible, error::Error, io};
+
+use crate::{Merged, MultiTracedError, NoneError};
+
+/// The HTTP status code an error should be reported with. Consulted by the `axum`, `actix` and `tonic` integrations when converting a `TracedError` into a response.
+/// The default implementation returns `500`, so error types that don't need other status codes can simply use `impl HttpStatus for MyError {}`.
+pub trait HttpStatus {
+    fn status(&self) -> u16 {
+        500
+    }
+}
+
+impl HttpStatus for String {}
+
+impl HttpStatus for &str {}
+
+impl HttpStatus for Box<dyn Error> {}
+
+impl HttpStatus for Box<dyn Error + Send + Sync> {}
+
+impl HttpStatus for NoneError {}
+
+impl HttpStatus for Infallible {}
+
+/// Maps `NotFound`, `PermissionDenied` and `InvalidInput` errors to `404`, `403` and `400` respectively.
+impl HttpStatus for io::Error {
+    fn status(&self) -> u16 {
+        match self.kind() {
+            io::ErrorKind::NotFound => 404,
+            io::ErrorKind::PermissionDenied => 403,
+            io::ErrorKind::InvalidInput => 400,
+            _ => 500,
+        }
+    }
+}
+
+/// Uses the highest status of all errors.
+impl<E: HttpStatus> HttpStatus for MultiTracedError<E> {
+    fn status(&self) -> u16 {
+        self.iter()
+            .map(|error| error.inner.status())
+            .max()
+            .unwrap_or(500)
+    }
+}
+
+/// Uses the higher status of both errors.
+impl<E: HttpStatus, F: HttpStatus> HttpStatus for Merged<E, F> {
+    fn status(&self) -> u16 {
+        self.first()
+            .inner
+            .status()
+            .max(self.second().inner.status())
+    }
+}
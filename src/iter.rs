@@ -0,0 +1,189 @@
+use std::panic::Location;
+
+use crate::{
+    capture_enabled, Frame, FrameKind, MultiTracedError, Trace, TraceFrame, TracedError,
+    TracedResult,
+};
+
+/// Separate the `Ok` values of an iterator of `TracedResult`s from its errors in a single pass.
+pub fn partition_traced<I, T, E>(iter: I) -> (Vec<T>, Vec<TracedError<E>>)
+where
+    I: IntoIterator<Item = TracedResult<T, E>>,
+{
+    iter.into_iter().partition_traced()
+}
+
+/// Extension trait for iterators over `TracedResult`s.
+pub trait TracedIterator<T, E>: Iterator<Item = TracedResult<T, E>> + Sized {
+    /// Separate the `Ok` values from the errors in a single pass. Both keep the order of the iterator.
+    fn partition_traced(self) -> (Vec<T>, Vec<TracedError<E>>) {
+        let mut oks = Vec::new();
+        let mut errors = Vec::new();
+        for item in self {
+            match item {
+                TracedResult::Ok(ok) => oks.push(ok),
+                TracedResult::Err(err) => errors.push(err),
+            }
+        }
+        (oks, errors)
+    }
+
+    /// Like `partition_traced`, but collects the errors into a `MultiTracedError`.
+    fn partition_traced_multi(self) -> (Vec<T>, MultiTracedError<E>) {
+        let (oks, errors) = self.partition_traced();
+        (oks, errors.into())
+    }
+
+    /// Collect all `Ok` values without short-circuiting, or, if any item is an `Err`, every error along with its call stack.
+    /// The call stack of the returned error starts at the caller location of this method.
+    #[track_caller]
+    fn collect_all<C: FromIterator<T>>(self) -> TracedResult<C, MultiTracedError<E>> {
+        let (oks, errors) = self.partition_traced();
+        if errors.is_empty() {
+            TracedResult::Ok(oks.into_iter().collect())
+        } else {
+            TracedResult::Err(TracedError::new(errors.into()))
+        }
+    }
+
+    /// Yield only the `Ok` values, discarding all errors.
+    fn oks(self) -> impl Iterator<Item = T> {
+        self.filter_map(|item| match item {
+            TracedResult::Ok(ok) => Some(ok),
+            TracedResult::Err(_) => None,
+        })
+    }
+
+    /// Yield only the errors, discarding all `Ok` values.
+    fn errors(self) -> impl Iterator<Item = TracedError<E>> {
+        self.filter_map(|item| match item {
+            TracedResult::Ok(_) => None,
+            TracedResult::Err(err) => Some(err),
+        })
+    }
+}
+
+impl<I, T, E> TracedIterator<T, E> for I where I: Iterator<Item = TracedResult<T, E>> {}
+
+/// Extension trait for iterators whose items are processed by closures returning `TracedResult`s.
+pub trait TryTracedIterator: Iterator + Sized {
+    /// Like `Iterator::try_fold`, but for closures returning a `TracedResult`.
+    /// If the closure fails, iteration stops and the caller location of this method is added to the error's call stack.
+    #[track_caller]
+    fn try_fold_traced<B, E, F>(self, init: B, mut f: F) -> TracedResult<B, E>
+    where
+        F: FnMut(B, Self::Item) -> TracedResult<B, E>,
+    {
+        let mut acc = init;
+        for item in self {
+            match f(acc, item) {
+                TracedResult::Ok(next) => acc = next,
+                TracedResult::Err(mut err) => {
+                    err.trace.record(FrameKind::Propagation);
+                    return TracedResult::Err(err);
+                }
+            }
+        }
+        TracedResult::Ok(acc)
+    }
+
+    /// Like `Iterator::try_for_each`, but for closures returning a `TracedResult`.
+    /// If the closure fails, iteration stops and the caller location of this method is added to the error's call stack.
+    #[track_caller]
+    fn try_for_each_traced<E, F>(self, mut f: F) -> TracedResult<(), E>
+    where
+        F: FnMut(Self::Item) -> TracedResult<(), E>,
+    {
+        for item in self {
+            if let TracedResult::Err(mut err) = f(item) {
+                err.trace.record(FrameKind::Propagation);
+                return TracedResult::Err(err);
+            }
+        }
+        TracedResult::Ok(())
+    }
+}
+
+impl<I: Iterator> TryTracedIterator for I {}
+
+/// Items that can be collected using `TracedCollect::traced_collect`: `std::result::Result`s and `TracedResult`s.
+pub trait TracedItem {
+    type Ok;
+    type Error;
+
+    /// Convert this item into a `TracedResult`. A plain error gets a call stack starting at `location`, while the call stack of a `TracedError` is continued with `location`.
+    fn into_traced_at(
+        self,
+        location: &'static Location<'static>,
+    ) -> TracedResult<Self::Ok, Self::Error>;
+}
+
+impl<T, E> TracedItem for Result<T, E> {
+    type Ok = T;
+    type Error = E;
+
+    fn into_traced_at(self, location: &'static Location<'static>) -> TracedResult<T, E> {
+        match self {
+            Ok(ok) => TracedResult::Ok(ok),
+            Err(err) => {
+                let frames = if capture_enabled() {
+                    vec![Frame::from(location).with_kind(FrameKind::Origin)]
+                } else {
+                    Vec::new()
+                };
+                TracedResult::Err(TracedError {
+                    trace: Trace::new(frames),
+                    inner: err,
+                    attachments: Default::default(),
+                })
+            }
+        }
+    }
+}
+
+impl<T, E> TracedItem for TracedResult<T, E> {
+    type Ok = T;
+    type Error = E;
+
+    fn into_traced_at(self, location: &'static Location<'static>) -> TracedResult<T, E> {
+        match self {
+            TracedResult::Ok(ok) => TracedResult::Ok(ok),
+            TracedResult::Err(mut err) => {
+                err.trace.record_at(location, FrameKind::Propagation);
+                TracedResult::Err(err)
+            }
+        }
+    }
+}
+
+/// Extension trait to collect iterators of `Result`s or `TracedResult`s into a `TracedResult`.
+pub trait TracedCollect: Iterator + Sized
+where
+    Self::Item: TracedItem,
+{
+    /// Collect the `Ok` values into `C`, stopping at the first error: `let values = iter.traced_collect::<Vec<_>>()?;`
+    /// The caller location of this method becomes the origin of a plain error, or is added to the call stack of a `TracedError`.
+    #[track_caller]
+    fn traced_collect<C>(self) -> TracedResult<C, <Self::Item as TracedItem>::Error>
+    where
+        C: FromIterator<<Self::Item as TracedItem>::Ok>,
+    {
+        let location = Location::caller();
+        let mut error = None;
+        let collected = self
+            .map_while(|item| match item.into_traced_at(location) {
+                TracedResult::Ok(ok) => Some(ok),
+                TracedResult::Err(err) => {
+                    error = Some(err);
+                    None
+                }
+            })
+            .collect();
+        match error {
+            None => TracedResult::Ok(collected),
+            Some(err) => TracedResult::Err(err),
+        }
+    }
+}
+
+impl<I: Iterator> TracedCollect for I where I::Item: TracedItem {}
@@ -1,18 +1,587 @@
-#![feature(try_trait_v2)]
+#![feature(try_trait_v2, try_trait_v2_residual, try_trait_v2_yeet)]
 
 use std::{
+    any::type_name,
     convert::Infallible,
-    fmt::Debug,
-    ops::{ControlFlow, FromResidual},
+    fmt::{Debug, Display},
+    ops::{ControlFlow, FromResidual, Residual, Yeet},
     panic::Location,
 };
 
+#[cfg(feature = "actix")]
+pub mod actix;
+#[cfg(any(feature = "proptest", feature = "quickcheck"))]
+mod arbitrary;
+mod attachment;
+#[cfg(feature = "axum")]
+pub mod axum;
+pub mod build_info;
+pub mod cli;
+pub mod collector;
+mod config;
+pub mod crash;
+pub mod diagnostics;
+#[cfg(feature = "error-stack")]
+mod error_stack;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod fingerprint;
+pub mod format;
+pub mod fs;
+pub mod http;
+pub mod iter;
+#[cfg(feature = "log")]
+mod log;
+mod merge;
+mod metadata;
+mod multi;
+mod option;
+#[cfg(feature = "otel")]
+mod otel;
+pub mod panic;
+#[cfg(feature = "pool")]
+mod pool;
+pub mod process;
+#[cfg(feature = "pyo3")]
+mod pyo3;
+#[cfg(feature = "rayon")]
+pub mod rayon;
+pub mod reporter;
+pub mod retry;
+#[cfg(feature = "sentry")]
+pub mod sentry;
+mod shared;
+pub mod syslog;
+pub mod testing;
+mod thin;
+pub mod thread;
+#[cfg(feature = "tonic")]
+pub mod tonic;
+#[cfg(feature = "tower")]
+pub mod tower;
+#[cfg(feature = "tracing-subscriber")]
+pub mod tracing_subscriber;
+#[cfg(feature = "wasm")]
+mod wasm;
+pub mod wire;
+
+use config::records_frame;
+pub use config::{
+    capture_enabled, set_capture_enabled, ColorChoice, Config, FrameFilter, FrameOrder,
+};
+pub use http::HttpStatus;
+pub use merge::Merged;
+pub use metadata::Severity;
+pub use multi::MultiTracedError;
+pub use option::TracedOption;
+pub use shared::SharedTracedError;
+pub use thin::ThinTracedResult;
+#[cfg(feature = "macros")]
+pub use traced_result_macros::{traced, traced_test};
+
+/// Attributes that complement `#[traced]`. With `use traced_result::traced;`, they can be used as `#[traced::skip]`.
+#[cfg(feature = "macros")]
+pub mod traced {
+    pub use traced_result_macros::skip;
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use std::{ops::RangeInclusive, panic::Location};
+
+    use crate::{FrameKind, TraceFrame, TracedError, TracedResult};
+
+    /// Used by `#[traced_test]` to fail tests returning an `Err`.
+    #[track_caller]
+    pub fn finish_test<T, E: std::fmt::Display>(result: TracedResult<T, E>) {
+        if let TracedResult::Err(err) = result {
+            panic!("test failed with a traced error: {err}");
+        }
+    }
+
+    /// Used by `traced_new!` to create an error whose origin frame contains the module path and crate.
+    #[track_caller]
+    pub fn new_in_module<E>(
+        inner: E,
+        module_path: &'static str,
+        crate_name: &'static str,
+        crate_version: &'static str,
+    ) -> TracedError<E> {
+        let mut err = TracedError::new(inner);
+        if let Some(origin) = err.trace.first_mut() {
+            *origin = origin
+                .with_module_path(module_path)
+                .with_crate(crate_name, crate_version);
+        }
+        err
+    }
+
+    /// Used by `#[traced]` to set the function and crate of all frames recorded within its lines of `file`.
+    pub fn name_frames<T, E>(
+        mut result: TracedResult<T, E>,
+        function: &'static str,
+        (crate_name, crate_version): (&'static str, &'static str),
+        file: &'static str,
+        lines: RangeInclusive<u32>,
+    ) -> TracedResult<T, E> {
+        if let TracedResult::Err(err) = &mut result {
+            for frame in err.trace.iter_mut() {
+                if frame.function.is_none() && frame.file == file && lines.contains(&frame.line) {
+                    *frame = frame
+                        .with_function(function)
+                        .with_crate(crate_name, crate_version);
+                }
+            }
+        }
+        result
+    }
+
+    /// Used by `#[traced::skip]` to remove all frames recorded within its lines of `file`, replacing the origin with `caller` if it was among them.
+    pub fn skip_frames<T, E, F: TraceFrame>(
+        mut result: TracedResult<T, E, F>,
+        caller: &'static Location<'static>,
+        file: &'static str,
+        lines: RangeInclusive<u32>,
+    ) -> TracedResult<T, E, F> {
+        if let TracedResult::Err(err) = &mut result {
+            let skipped = |frame: &F| {
+                let (frame_file, line, _) = frame.location();
+                frame_file == file && lines.contains(&line)
+            };
+            let origin_skipped = err.trace.first().is_some_and(skipped);
+            err.trace.retain(|frame| !skipped(frame));
+            if origin_skipped {
+                err.trace
+                    .insert(0, F::from(caller).with_kind(FrameKind::Origin));
+            }
+        }
+        result
+    }
+}
+
+/// Re-exports the types and extension traits needed by most code using this crate: `use traced_result::prelude::*;`
+pub mod prelude {
+    pub use crate::{
+        assert_err_at,
+        iter::{TracedCollect, TracedIterator, TryTracedIterator},
+        process::CommandExt,
+        traced_new, IntoTraced, NoneError, OkOrTraced, ParseTraced, ResumeTrace, ThinTracedResult,
+        Traced, TracedError, TracedOption, TracedResult, TryIntoTraced,
+    };
+}
+
+/// Creates a `build_info::BuildInfo` describing the current crate and build.
+/// The git hash is read from the `GIT_HASH` environment variable at compile time, which can be set by a build script using `cargo:rustc-env=GIT_HASH=...`.
+#[macro_export]
+macro_rules! build_info {
+    () => {
+        $crate::build_info::BuildInfo {
+            crate_name: ::core::env!("CARGO_PKG_NAME"),
+            crate_version: ::core::env!("CARGO_PKG_VERSION"),
+            git_hash: ::core::option_env!("GIT_HASH"),
+            profile: if ::core::cfg!(debug_assertions) {
+                "debug"
+            } else {
+                "release"
+            },
+            target_arch: ::std::env::consts::ARCH,
+            target_os: ::std::env::consts::OS,
+        }
+    };
+}
+
+/// Creates a `TracedError` like `TracedError::new`, but also records the path of the current module and the name and version of the current crate with its origin frame.
+#[macro_export]
+macro_rules! traced_new {
+    ($err:expr) => {
+        $crate::__private::new_in_module(
+            $err,
+            ::core::module_path!(),
+            ::core::env!("CARGO_PKG_NAME"),
+            ::core::env!("CARGO_PKG_VERSION"),
+        )
+    };
+}
+
+/// Asserts that a `TracedResult` is an `Err` whose call stack contains a frame in the specified file, optionally restricted to a range of lines.
+/// The file is matched against the end of each frame's path, so `"config.rs"` matches `src/config.rs`. Lines can be given as any range of `u32`s, e.g. `assert_err_at!(load_config(), "config.rs", 40..=60)`.
+/// On success, the macro evaluates to the `TracedError`.
+#[macro_export]
+macro_rules! assert_err_at {
+    ($result:expr, $file:expr $(,)?) => {
+        $crate::assert_err_at!($result, $file, ..)
+    };
+    ($result:expr, $file:expr, $lines:expr $(,)?) => {
+        match $result {
+            $crate::TracedResult::Ok(_) => {
+                panic!("assertion failed: `{}` is not an `Err`", stringify!($result))
+            }
+            $crate::TracedResult::Err(err) => {
+                let (file, lines) = ($file, $lines);
+                let found = err.trace().iter().any(|frame| {
+                    ::std::path::Path::new(frame.file()).ends_with(file)
+                        && ::std::ops::RangeBounds::contains(&lines, &frame.line())
+                });
+                if !found {
+                    let trace: ::std::vec::Vec<_> =
+                        err.trace().iter().map(|frame| frame.to_string()).collect();
+                    panic!(
+                        "assertion failed: the call stack of `{}` has no frame in {} (lines {:?}):\n{}",
+                        stringify!($result),
+                        file,
+                        lines,
+                        trace.join("\n")
+                    );
+                }
+                err
+            }
+        }
+    };
+}
+
+/// The reason a `Frame` was added to a call stack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FrameKind {
+    /// The error was created at this location.
+    Origin,
+    /// The error was propagated at this location, e.g. using the `?` operator.
+    Propagation,
+    /// The error was converted from one type into another at this location, e.g. by the `?` operator using `From`. Contains the names of both types as returned by `std::any::type_name`.
+    Conversion {
+        from: &'static str,
+        to: &'static str,
+    },
+    /// The error was created by merging two other errors at this location. See `TracedError::merge`.
+    Merge,
+    /// An attempt of `retry::retry` failed at this location. Contains the number of the attempt, starting at 1.
+    Retry { attempt: u32 },
+    /// The error was received from another process at this location. See `process::parse_report`.
+    ProcessBoundary,
+}
+
+/// A single entry in the call stack of a `TracedError`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Frame {
+    file: &'static str,
+    line: u32,
+    #[cfg(not(feature = "no-columns"))]
+    column: u32,
+    kind: FrameKind,
+    function: Option<&'static str>,
+    module_path: Option<&'static str>,
+    crate_name: Option<&'static str>,
+    crate_version: Option<&'static str>,
+}
+
+impl Frame {
+    /// Create a frame at an arbitrary location. Frames are usually recorded automatically, but this can be useful to create deterministic call stacks in tests.
+    /// With the `no-columns` feature enabled, `column` is ignored.
+    pub fn new(file: &'static str, line: u32, column: u32, kind: FrameKind) -> Self {
+        #[cfg(feature = "no-columns")]
+        let _ = column;
+        Self {
+            file,
+            line,
+            #[cfg(not(feature = "no-columns"))]
+            column,
+            kind,
+            function: None,
+            module_path: None,
+            crate_name: None,
+            crate_version: None,
+        }
+    }
+
+    /// Set the path of the function this frame was recorded in. Usually done by the `#[traced]` attribute.
+    pub fn with_function(self, function: &'static str) -> Self {
+        Self {
+            function: Some(function),
+            ..self
+        }
+    }
+
+    /// Set the path of the module this frame was recorded in. Usually done by the `traced_new!` macro.
+    pub fn with_module_path(self, module_path: &'static str) -> Self {
+        Self {
+            module_path: Some(module_path),
+            ..self
+        }
+    }
+
+    /// Set the name and version of the crate this frame was recorded in. Usually done by the `traced_new!` macro and the `#[traced]` attribute.
+    pub fn with_crate(self, name: &'static str, version: &'static str) -> Self {
+        Self {
+            crate_name: Some(name),
+            crate_version: Some(version),
+            ..self
+        }
+    }
+
+    #[inline(always)]
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    #[inline(always)]
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// The column of this frame, or `0` if the `no-columns` feature is enabled.
+    #[inline(always)]
+    pub fn column(&self) -> u32 {
+        #[cfg(not(feature = "no-columns"))]
+        return self.column;
+        #[cfg(feature = "no-columns")]
+        return 0;
+    }
+
+    #[inline(always)]
+    pub fn kind(&self) -> FrameKind {
+        self.kind
+    }
+
+    /// The path of the function this frame was recorded in, if known.
+    #[inline(always)]
+    pub fn function(&self) -> Option<&'static str> {
+        self.function
+    }
+
+    /// The path of the module this frame was recorded in, if known.
+    #[inline(always)]
+    pub fn module_path(&self) -> Option<&'static str> {
+        self.module_path
+    }
+
+    /// The name of the crate this frame was recorded in, if known.
+    #[inline(always)]
+    pub fn crate_name(&self) -> Option<&'static str> {
+        self.crate_name
+    }
+
+    /// The version of the crate this frame was recorded in, if known.
+    #[inline(always)]
+    pub fn crate_version(&self) -> Option<&'static str> {
+        self.crate_version
+    }
+}
+
+/// Creates a `FrameKind::Propagation` frame at `location`.
+impl From<&'static Location<'static>> for Frame {
+    fn from(location: &'static Location<'static>) -> Self {
+        Self::new(
+            location.file(),
+            location.line(),
+            location.column(),
+            FrameKind::Propagation,
+        )
+    }
+}
+
+impl TraceFrame for Frame {
+    fn with_kind(self, kind: FrameKind) -> Self {
+        Self { kind, ..self }
+    }
+
+    fn location(&self) -> (&str, u32, u32) {
+        (self.file, self.line, self.column())
+    }
+
+    #[cfg(feature = "pool")]
+    fn alloc_frames() -> Vec<Self> {
+        pool::take()
+    }
+
+    #[cfg(feature = "pool")]
+    fn free_frames(frames: Vec<Self>) {
+        pool::recycle(frames);
+    }
+}
+
+/// A type that can be recorded in the call stack of a `TracedError` in place of `Frame`, e.g. to capture a request id at every location an error passes through: `TracedResult<T, E, MyFrame>`.
+/// The `?` operator and the basic methods of `TracedError` and `TracedResult` work with any frame type, while formatting and most integrations require `Frame`. Errors with custom frames are created using `TracedError::new_custom`.
+pub trait TraceFrame: From<&'static Location<'static>> {
+    /// Set the reason this frame was recorded. Called right after the frame was created from its location.
+    fn with_kind(self, kind: FrameKind) -> Self;
+
+    /// The file, line and column this frame was recorded at.
+    fn location(&self) -> (&str, u32, u32);
+
+    /// An empty buffer for a new call stack. Only overridden by `Frame` to reuse buffers with the `pool` feature.
+    #[doc(hidden)]
+    fn alloc_frames() -> Vec<Self>
+    where
+        Self: Sized,
+    {
+        Vec::new()
+    }
+
+    /// Free the buffer of a dropped call stack. See `alloc_frames`.
+    #[doc(hidden)]
+    fn free_frames(frames: Vec<Self>)
+    where
+        Self: Sized,
+    {
+        drop(frames);
+    }
+}
+
+/// Formats the frame's location as `file:line:column`, or `file:line` if the `no-columns` feature is enabled.
+impl std::fmt::Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)?;
+        if !cfg!(feature = "no-columns") {
+            write!(f, ":{}", self.column())?;
+        }
+        Ok(())
+    }
+}
+
+/// The call stack of a `TracedError`.
+/// With the `drop-guard` feature enabled, this also keeps track of whether the call stack was ever observed, and logs a warning if it is dropped without having been observed in debug builds.
+/// In strict mode (the `strict` feature, `Config::strict` or `TRACED_RESULT_STRICT=1`), it panics instead.
+struct Trace<F: TraceFrame = Frame> {
+    frames: Vec<F>,
+    #[cfg(all(feature = "drop-guard", debug_assertions))]
+    observed: std::sync::atomic::AtomicBool,
+}
+
+impl<F: TraceFrame> Trace<F> {
+    fn new(frames: Vec<F>) -> Self {
+        Self {
+            frames,
+            #[cfg(all(feature = "drop-guard", debug_assertions))]
+            observed: Default::default(),
+        }
+    }
+
+    /// Create a call stack starting with a frame of the specified kind at the caller location of this method.
+    /// If capturing is disabled (see `set_capture_enabled`), the call stack is empty and nothing is allocated.
+    #[track_caller]
+    fn starting_at(kind: FrameKind) -> Self {
+        if capture_enabled() {
+            let mut frames = F::alloc_frames();
+            frames.push(F::from(Location::caller()).with_kind(kind));
+            Self::new(frames)
+        } else {
+            Self::new(Vec::new())
+        }
+    }
+
+    /// Mark the error this call stack belongs to as handled.
+    #[inline(always)]
+    fn observe(&self) {
+        #[cfg(all(feature = "drop-guard", debug_assertions))]
+        self.observed
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Add a frame of the specified kind at the caller location of this method to the call stack. See `record_at`.
+    #[track_caller]
+    fn record(&mut self, kind: FrameKind) {
+        self.record_at(Location::caller(), kind);
+    }
+
+    /// Add a frame of the specified kind at `location` to the call stack, unless only origins are recorded (see `Config::origin_only`), it already reached the maximum depth set using `Config::max_depth`, or the location is rejected by `Config::frame_filter`.
+    fn record_at(&mut self, location: &'static Location<'static>, kind: FrameKind) {
+        if records_frame(location, self.frames.len()) {
+            self.frames.push(F::from(location).with_kind(kind));
+        }
+    }
+
+    /// Add a `FrameKind::Propagation` frame at the caller location of this method if combinators are traced (see `Config::trace_combinators`).
+    #[track_caller]
+    fn record_combinator(&mut self) {
+        if cfg!(feature = "trace-combinators") || Config::current().trace_combinators {
+            self.record(FrameKind::Propagation);
+        }
+    }
+
+    fn into_frames(mut self) -> Vec<F> {
+        self.observe();
+        std::mem::take(&mut self.frames)
+    }
+}
+
+impl<F: TraceFrame> std::ops::Deref for Trace<F> {
+    type Target = Vec<F>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.frames
+    }
+}
+
+impl<F: TraceFrame> std::ops::DerefMut for Trace<F> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.frames
+    }
+}
+
+impl<F: TraceFrame + Clone> Clone for Trace<F> {
+    fn clone(&self) -> Self {
+        let clone = Self::new(self.frames.clone());
+        #[cfg(all(feature = "drop-guard", debug_assertions))]
+        if self.observed.load(std::sync::atomic::Ordering::Relaxed) {
+            clone.observe();
+        }
+        clone
+    }
+}
+
+impl<F: TraceFrame + Debug> Debug for Trace<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.observe();
+        self.frames.fmt(f)
+    }
+}
+
+#[cfg(any(all(feature = "drop-guard", debug_assertions), feature = "pool"))]
+impl<F: TraceFrame> Drop for Trace<F> {
+    fn drop(&mut self) {
+        #[cfg(all(feature = "drop-guard", debug_assertions))]
+        self.check_observed();
+        F::free_frames(std::mem::take(&mut self.frames));
+    }
+}
+
+#[cfg(all(feature = "drop-guard", debug_assertions))]
+impl<F: TraceFrame> Trace<F> {
+    /// Warn about (or, in strict mode, panic because of) an error that is dropped without having been handled.
+    fn check_observed(&mut self) {
+        if *self.observed.get_mut() {
+            return;
+        }
+
+        let mut message = String::from("A traced error was dropped without being handled");
+        for frame in self.frames.iter().rev() {
+            let (file, line, col) = frame.location();
+            message.push_str(&format!("\nAt ({line}:{col}) in {file}"));
+        }
+        let strict = cfg!(feature = "strict")
+            || Config::current().strict
+            || std::env::var_os("TRACED_RESULT_STRICT").is_some_and(|value| value == "1");
+        // Panicking while already unwinding would abort the process and hide the original panic
+        if strict && !std::thread::panicking() {
+            panic!("{message}");
+        }
+        let origin = self.frames.first().map(TraceFrame::location);
+        ::log::warn!(
+            origin_file = origin.map(|(file, _, _)| file),
+            origin_line = origin.map(|(_, line, _)| line),
+            frame_count = self.frames.len();
+            "{message}"
+        );
+    }
+}
+
 /// A wrapper class that stores an error as well as a call stack associated with it.
-/// This call stack is guaranteed to contain at least the location of this error's construction (see `new`), and, if used with a `TracedResult`, will also contain the source location of every position where it was propagated using the `?` operator. See `TracedResult` for more info.
-#[derive(Debug)]
-pub struct TracedError<E> {
-    trace: Vec<&'static Location<'static>>,
+/// The call stack usually starts at the location of this error's construction (see `new`), and, if used with a `TracedResult`, also contains the source location of every position where it was propagated using the `?` operator. See `TracedResult` for more info.
+/// It can be empty, e.g. if recording was disabled using `set_capture_enabled` when the error was created, so code inspecting it should not assume an origin is present.
+#[derive(Debug, Clone)]
+pub struct TracedError<E, F: TraceFrame = Frame> {
+    trace: Trace<F>,
     inner: E,
+    attachments: attachment::Attachments,
 }
 
 impl<E> TracedError<E> {
@@ -20,24 +589,111 @@ impl<E> TracedError<E> {
     /// The caller location of this method will become the first entry in its call stack.
     #[track_caller]
     pub fn new(inner: E) -> Self {
-        let trace = vec![Location::caller()];
-        Self { trace, inner }
+        Self::new_custom(inner)
+    }
+
+    /// Like `new`, but also setting the severity of the error. See `with_severity`.
+    #[track_caller]
+    pub fn new_with_severity(inner: E, severity: Severity) -> Self {
+        Self::new_custom(inner).with_severity(severity)
+    }
+
+    /// Whether the call stack contains a frame in `file`. Paths are compared by their components from the end, so `"config.rs"` matches `src/config.rs`, but not `src/myconfig.rs`.
+    pub fn contains_file(&self, file: impl AsRef<std::path::Path>) -> bool {
+        let file = file.as_ref();
+        self.trace()
+            .iter()
+            .any(|frame| std::path::Path::new(frame.file()).ends_with(file))
+    }
+
+    /// The first frame of the call stack (starting at the origin) for which `predicate` returns `true`.
+    pub fn find_frame(&self, mut predicate: impl FnMut(&Frame) -> bool) -> Option<&Frame> {
+        self.trace().iter().find(|frame| predicate(frame))
+    }
+
+    /// The frames of the call stack that were recorded in the crate called `name`, starting at the origin.
+    /// The crate of a frame is only known if it was recorded by `traced_new!` or in a function annotated with `#[traced]` (see `Frame::crate_name` and `Frame::module_path`).
+    pub fn frames_in_crate<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Frame> + 'a {
+        self.trace().iter().filter(move |frame| {
+            frame.crate_name() == Some(name)
+                || frame
+                    .module_path()
+                    .is_some_and(|path| path.split("::").next() == Some(name))
+        })
+    }
+}
+
+impl<E, F: TraceFrame> TracedError<E, F> {
+    /// Like `new`, but for errors recording a custom frame type: `TracedError::<_, MyFrame>::new_custom(error)`.
+    #[track_caller]
+    pub fn new_custom(inner: E) -> Self {
+        let trace = Trace::starting_at(FrameKind::Origin);
+        Self {
+            trace,
+            inner,
+            attachments: Default::default(),
+        }
     }
 
     /// Get the error's value, discarding the call stack associated with it.    
     #[inline(always)]
     pub fn into_inner(self) -> E {
+        self.trace.observe();
         self.inner
     }
 
-    pub fn trace(&self) -> &Vec<&'static Location<'static>> {
+    pub fn trace(&self) -> &[F] {
+        self.trace.observe();
         &self.trace
     }
 
+    /// Pass the current call stack to `f` and return `self`, e.g. to log it conditionally in the middle of a chain of calls.
+    pub fn inspect_trace(self, f: impl FnOnce(&[F])) -> Self {
+        f(self.trace());
+        self
+    }
+
     /// Convert the `TracedError` into a tuple of error and call stack.
     #[inline(always)]
-    pub fn split(self) -> (E, Vec<&'static Location<'static>>) {
-        (self.inner, self.trace)
+    pub fn split(self) -> (E, Vec<F>) {
+        (self.inner, self.trace.into_frames())
+    }
+
+    /// Remove all frames from the call stack and return them, e.g. to log them separately. The error itself is kept.
+    pub fn take_trace(&mut self) -> Vec<F> {
+        self.trace.observe();
+        std::mem::take(&mut self.trace.frames)
+    }
+
+    /// Remove all frames from the call stack.
+    pub fn clear_trace(&mut self) {
+        self.trace.observe();
+        self.trace.clear();
+    }
+
+    /// Keep only the first `len` frames of the call stack, starting at the origin, e.g. for errors that are stored for a long time and would otherwise keep accumulating frames.
+    pub fn truncate_trace(&mut self, len: usize) {
+        self.trace.truncate(len);
+    }
+
+    /// Convert the error into another type using `From`.
+    /// If the types differ, a `FrameKind::Conversion` frame with the caller location of this method is added to the call stack.
+    #[track_caller]
+    fn convert<U: From<E>>(self) -> TracedError<U, F> {
+        let TracedError {
+            mut trace,
+            inner,
+            attachments,
+        } = self;
+        let (from, to) = (type_name::<E>(), type_name::<U>());
+        if from != to {
+            trace.record(FrameKind::Conversion { from, to });
+        }
+        TracedError {
+            trace,
+            inner: From::from(inner),
+            attachments,
+        }
     }
 }
 
@@ -48,39 +704,115 @@ impl<E> From<E> for TracedError<E> {
     }
 }
 
+/// Formats the error followed by its call stack, most recent frame first. Use `display_with` for other formats.
 impl<E: std::fmt::Display> std::fmt::Display for TracedError<E> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.inner.fmt(f)?;
-
-        for location in self.trace.iter().rev() {
-            writeln!(
-                f,
-                "At ({line}:{col}) in {file}",
-                file = location.file(),
-                line = location.line(),
-                col = location.column()
-            )?;
-        }
-        Ok(())
+        self.display_with(format::FormatOptions::default()).fmt(f)
     }
 }
 
 impl<E: std::error::Error> std::error::Error for TracedError<E> {}
 
+impl<E: std::error::Error + Send + Sync + 'static> TracedError<E> {
+    /// Wrap this error in an `io::Error` of kind `Other`, e.g. to return it from a `Read` or `Write` implementation. The call stack is kept; use `from_io_error` to get the `TracedError` back.
+    pub fn into_io_error(self) -> std::io::Error {
+        std::io::Error::other(self)
+    }
+
+    /// Recover a `TracedError` wrapped using `into_io_error`, adding the caller location of this method to its call stack. Returns `error` unchanged if it doesn't wrap a `TracedError<E>`.
+    #[track_caller]
+    pub fn from_io_error(error: std::io::Error) -> Result<Self, std::io::Error> {
+        let mut error = error.downcast::<Self>()?;
+        error.trace.record(FrameKind::Propagation);
+        Ok(error)
+    }
+}
+
+/// See `TracedError::into_io_error`.
+impl<E: std::error::Error + Send + Sync + 'static> From<TracedError<E>> for std::io::Error {
+    fn from(error: TracedError<E>) -> Self {
+        error.into_io_error()
+    }
+}
+
+impl<E, F: TraceFrame> AsRef<E> for TracedError<E, F> {
+    fn as_ref(&self) -> &E {
+        self.trace.observe();
+        &self.inner
+    }
+}
+
+impl<E, F: TraceFrame> AsMut<E> for TracedError<E, F> {
+    fn as_mut(&mut self) -> &mut E {
+        self.trace.observe();
+        &mut self.inner
+    }
+}
+
+impl<E, F: TraceFrame> std::borrow::Borrow<E> for TracedError<E, F> {
+    fn borrow(&self) -> &E {
+        self.trace.observe();
+        &self.inner
+    }
+}
+
+impl<E, F: TraceFrame> std::borrow::BorrowMut<E> for TracedError<E, F> {
+    fn borrow_mut(&mut self) -> &mut E {
+        self.trace.observe();
+        &mut self.inner
+    }
+}
+
+/// Allows calling methods of the inner error directly on the `TracedError`, e.g. `error.kind()` for a `TracedError<io::Error>`.
+impl<E, F: TraceFrame> std::ops::Deref for TracedError<E, F> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        self.trace.observe();
+        &self.inner
+    }
+}
+
+impl<E, F: TraceFrame> std::ops::DerefMut for TracedError<E, F> {
+    fn deref_mut(&mut self) -> &mut E {
+        self.trace.observe();
+        &mut self.inner
+    }
+}
+
 /// A `Result` that traces the call stack of `Err` values.
 /// Every time an `Err` value is propagated using the `?` operator, `TracedResult`s custom `Try` implementation will automatically append the location of the `?` operator to the `TracedError`s call stack.
 /// Note that both `TracedError::new()` and `TracedResult::try()` use the `#[track_caller]` attribute to get their caller's location. This won't affect most users of this crate; However, if you use #[track_caller] on your own methods, you should be aware that the locations tracked by `trace_error` may be further up the stack than their "actual" locations. See [the Rust reference](https://doc.rust-lang.org/std/panic/struct.Location.html#method.caller) for more info.
-#[derive(Debug)]
-pub enum TracedResult<T, E> {
+#[derive(Debug, Clone)]
+#[must_use = "this `TracedResult` may be an `Err` variant, which should be handled"]
+pub enum TracedResult<T, E, F: TraceFrame = Frame> {
     Ok(T),
-    Err(TracedError<E>),
+    Err(TracedError<E, F>),
+}
+
+/// A `TracedResult` with a type-erased error by default, for application code that doesn't need a specific error type: `fn main() -> Traced<()>`.
+/// Since `Box<dyn Error + Send + Sync>` implements `From` for every error type, the `?` operator can be used on any traced result whose error type implements `Error + Send + Sync`.
+pub type Traced<T, E = Box<dyn std::error::Error + Send + Sync>> = TracedResult<T, E>;
+
+/// Allows returning a `TracedResult` from `main`. Like for `Result`, an `Err` value is printed to stderr and the process exits with a failure code; the error is printed using its `Display` implementation, so its call stack is included.
+impl<T: std::process::Termination, E: Display> std::process::Termination for TracedResult<T, E> {
+    fn report(self) -> std::process::ExitCode {
+        match self {
+            TracedResult::Ok(ok) => ok.report(),
+            TracedResult::Err(err) => {
+                eprint!("Error: {err}");
+                std::process::ExitCode::FAILURE
+            }
+        }
+    }
 }
 
-impl<T, E> TracedResult<T, E> {
+impl<T, E, F: TraceFrame> TracedResult<T, E, F> {
     /// Convert this `TracedResult<T, E>` into a `std::result::Result<T, TracedError<E>>`.
     /// This is useful when working with functions that do not support `TracedResult`, but causes the error's (if any) call stack to freeze, and subsequent uses of the `?` operator will no longer be tracked.
+    /// Use `ResumeTrace::resume()` to convert the result back into a `TracedResult` and continue tracking.
     #[inline(always)]
-    pub fn into_result(self) -> std::result::Result<T, TracedError<E>> {
+    pub fn into_result(self) -> std::result::Result<T, TracedError<E, F>> {
         match self {
             TracedResult::Ok(ok) => Ok(ok),
             TracedResult::Err(err) => Err(err),
@@ -107,27 +839,180 @@ impl<T, E> TracedResult<T, E> {
         matches!(self, TracedResult::Err(_))
     }
 
+    /// Borrow this result as a `std::result::Result`, e.g. to pass it to functions expecting one without consuming it or freezing its call stack.
+    #[inline(always)]
+    pub fn as_result(&self) -> std::result::Result<&T, &TracedError<E, F>> {
+        match self {
+            TracedResult::Ok(ok) => Ok(ok),
+            TracedResult::Err(err) => Err(err),
+        }
+    }
+
+    /// Mutably borrow this result as a `std::result::Result`. See `as_result`.
+    #[inline(always)]
+    pub fn as_result_mut(&mut self) -> std::result::Result<&mut T, &mut TracedError<E, F>> {
+        match self {
+            TracedResult::Ok(ok) => Ok(ok),
+            TracedResult::Err(err) => Err(err),
+        }
+    }
+
+    /// If this is an `Err` value, pass the error's current call stack to `f`. Returns `self` in either case. See `TracedError::inspect_trace`.
+    pub fn inspect_trace(self, f: impl FnOnce(&[F])) -> Self {
+        match self {
+            TracedResult::Err(err) => TracedResult::Err(err.inspect_trace(f)),
+            ok => ok,
+        }
+    }
+
+    /// Replace this result with `Ok(T::default())`, returning the previous result. Like `Option::take()`, this is useful for results stored in struct fields.
+    pub fn take(&mut self) -> Self
+    where
+        T: Default,
+    {
+        std::mem::replace(self, TracedResult::Ok(T::default()))
+    }
+
+    /// Replace this result with `Ok(value)`, returning the previous result. Equivalent to `Option::replace()`.
+    pub fn replace(&mut self, value: T) -> Self {
+        std::mem::replace(self, TracedResult::Ok(value))
+    }
+
     /// Equivalent to `std::result::Result::<T, TracedError<E>>::map()`
-    pub fn map<U>(self, map: impl FnOnce(T) -> U) -> TracedResult<U, E> {
+    /// If combinators are traced (see `Config::trace_combinators`), the caller location of this method is added to the call stack of an `Err`.
+    #[track_caller]
+    pub fn map<U>(self, map: impl FnOnce(T) -> U) -> TracedResult<U, E, F> {
         match self {
             TracedResult::Ok(ok) => TracedResult::Ok(map(ok)),
-            TracedResult::Err(err) => TracedResult::Err(err),
+            TracedResult::Err(mut err) => {
+                err.trace.record_combinator();
+                TracedResult::Err(err)
+            }
         }
     }
 
     /// Map the `Err` value of this result, if present.
-    /// This does **not** add the call location of this method to the stack trace.
-    pub fn map_err<F>(self, map: impl FnOnce(E) -> F) -> TracedResult<T, F> {
+    /// This only adds the call location of this method to the stack trace if combinators are traced (see `Config::trace_combinators`).
+    #[track_caller]
+    pub fn map_err<U>(self, map: impl FnOnce(E) -> U) -> TracedResult<T, U, F> {
+        match self {
+            TracedResult::Ok(ok) => TracedResult::Ok(ok),
+            TracedResult::Err(TracedError {
+                inner,
+                mut trace,
+                attachments,
+            }) => {
+                trace.record_combinator();
+                TracedResult::Err(TracedError {
+                    inner: map(inner),
+                    trace,
+                    attachments,
+                })
+            }
+        }
+    }
+
+    /// Like `map_err`, but `map` receives and returns the whole `TracedError`, e.g. to create an error that embeds or summarizes the call stack of the original one.
+    /// If combinators are traced (see `Config::trace_combinators`), the caller location of this method is added to the call stack of the returned error.
+    #[track_caller]
+    pub fn map_err_full<U>(
+        self,
+        map: impl FnOnce(TracedError<E, F>) -> TracedError<U, F>,
+    ) -> TracedResult<T, U, F> {
+        match self {
+            TracedResult::Ok(ok) => TracedResult::Ok(ok),
+            TracedResult::Err(err) => {
+                let mut err = map(err);
+                err.trace.record_combinator();
+                TracedResult::Err(err)
+            }
+        }
+    }
+
+    /// Equivalent to `std::result::Result::<T, TracedError<E>>::and_then()`
+    /// If combinators are traced (see `Config::trace_combinators`), the caller location of this method is added to the call stack of an `Err`, whether it was already present or returned by `op`.
+    #[track_caller]
+    pub fn and_then<U>(self, op: impl FnOnce(T) -> TracedResult<U, E, F>) -> TracedResult<U, E, F> {
+        match self.map(op) {
+            TracedResult::Ok(TracedResult::Ok(ok)) => TracedResult::Ok(ok),
+            TracedResult::Ok(TracedResult::Err(mut err)) => {
+                err.trace.record_combinator();
+                TracedResult::Err(err)
+            }
+            TracedResult::Err(err) => TracedResult::Err(err),
+        }
+    }
+
+    /// Like `std::result::Result::<T, TracedError<E>>::or_else()`, but `op` receives the entire `TracedError`, so it can keep its call stack.
+    /// If combinators are traced (see `Config::trace_combinators`), the caller location of this method is added to the call stack of an `Err` returned by `op`.
+    #[track_caller]
+    pub fn or_else<U>(
+        self,
+        op: impl FnOnce(TracedError<E, F>) -> TracedResult<T, U, F>,
+    ) -> TracedResult<T, U, F> {
+        match self {
+            TracedResult::Ok(ok) => TracedResult::Ok(ok),
+            TracedResult::Err(err) => match op(err) {
+                TracedResult::Ok(ok) => TracedResult::Ok(ok),
+                TracedResult::Err(mut err) => {
+                    err.trace.record_combinator();
+                    TracedResult::Err(err)
+                }
+            },
+        }
+    }
+
+    /// Convert the `Err` value of this result into another type using `From`, keeping its call stack.
+    /// Like the `?` operator, this adds a `FrameKind::Conversion` frame with the caller location of this method if the types differ.
+    #[track_caller]
+    pub fn err_into<U: From<E>>(self) -> TracedResult<T, U, F> {
         match self {
             TracedResult::Ok(ok) => TracedResult::Ok(ok),
-            TracedResult::Err(TracedError { inner, trace }) => TracedResult::Err(TracedError {
-                inner: map(inner),
-                trace,
-            }),
+            TracedResult::Err(err) => TracedResult::Err(err.convert()),
+        }
+    }
+
+    /// Map the `Ok` value of this result using a fallible function.
+    /// If `map` fails, its error becomes a `TracedError` whose call stack starts at the caller location of this method.
+    /// If this result already is an `Err` and combinators are traced (see `Config::trace_combinators`), the caller location of this method is added to its call stack.
+    #[track_caller]
+    pub fn try_map<U>(self, map: impl FnOnce(T) -> Result<U, E>) -> TracedResult<U, E, F> {
+        match self {
+            TracedResult::Ok(ok) => match map(ok) {
+                Ok(mapped) => TracedResult::Ok(mapped),
+                Err(err) => TracedResult::Err(TracedError::new_custom(err)),
+            },
+            TracedResult::Err(mut err) => {
+                err.trace.record_combinator();
+                TracedResult::Err(err)
+            }
+        }
+    }
+
+    /// Like `try_map`, but for functions returning a `TracedResult`.
+    /// If `map` fails, the caller location of this method is added to its error's call stack.
+    #[track_caller]
+    pub fn try_map_traced<U>(
+        self,
+        map: impl FnOnce(T) -> TracedResult<U, E, F>,
+    ) -> TracedResult<U, E, F> {
+        match self {
+            TracedResult::Ok(ok) => match map(ok) {
+                TracedResult::Ok(mapped) => TracedResult::Ok(mapped),
+                TracedResult::Err(mut err) => {
+                    err.trace.record(FrameKind::Propagation);
+                    TracedResult::Err(err)
+                }
+            },
+            TracedResult::Err(err) => TracedResult::Err(err),
         }
     }
 
-    /// Equivalent to `std::result::Result::<T, TracedError<E>>::map_or()`
+    /// Returns `default` if this result is an `Err`, or the result of applying `map` to the `Ok` value otherwise.
+    /// Unlike `std::result::Result::map_or()`, this takes the function first and the default second.
+    #[deprecated(
+        note = "the arguments are in the opposite order of `Result::map_or`; use `map_ok_or` instead"
+    )]
     pub fn map_or<U>(self, map: impl FnOnce(T) -> U, default: U) -> U {
         match self {
             TracedResult::Ok(ok) => map(ok),
@@ -135,10 +1020,19 @@ impl<T, E> TracedResult<T, E> {
         }
     }
 
-    /// Equivalent to `std::result::Result::<T, TracedError<E>>::map_or_else()`
+    /// Equivalent to `std::result::Result::<T, TracedError<E>>::map_or()`, with the same argument order.
+    /// The default is evaluated eagerly; use `map_or_else` to compute it from the error only if needed.
+    pub fn map_ok_or<U>(self, default: U, map: impl FnOnce(T) -> U) -> U {
+        match self {
+            TracedResult::Ok(ok) => map(ok),
+            TracedResult::Err(_) => default,
+        }
+    }
+
+    /// Equivalent to `std::result::Result::<T, TracedError<E>>::map_or_else()`, with the same argument order: `op` computes the default from the error, `map` maps the `Ok` value.
     pub fn map_or_else<U>(
         self,
-        op: impl FnOnce(TracedError<E>) -> U,
+        op: impl FnOnce(TracedError<E, F>) -> U,
         map: impl FnOnce(T) -> U,
     ) -> U {
         match self {
@@ -164,31 +1058,65 @@ impl<T, E> TracedResult<T, E> {
 
     /// Equivalent to `std::result::Result::<T, TracedError<E>>::else()`
     #[inline(always)]
-    pub fn unwrap_or_else(self, op: impl FnOnce(TracedError<E>) -> T) -> T {
+    pub fn unwrap_or_else(self, op: impl FnOnce(TracedError<E, F>) -> T) -> T {
         self.into_result().unwrap_or_else(op)
     }
 }
 
+impl<T, F: TraceFrame> TracedResult<T, Infallible, F> {
+    /// Equivalent to `std::result::Result::<T, Infallible>::into_ok()`.
+    /// Unlike `unwrap()`, this can never panic since the error type is uninhabited.
+    #[inline(always)]
+    pub fn into_ok(self) -> T {
+        match self {
+            TracedResult::Ok(ok) => ok,
+            TracedResult::Err(err) => match err.inner {},
+        }
+    }
+}
+
 // Standard `Result` methods.
 // Internally, all these use the actual std::result::Result methods. Conversion overhead for this should be basically zero since it's done using an inlined function with a single match expression.
 // The upside of this is that the panicking behavior of these methods will stay consistent with their `std` counterparts
 impl<T: Debug, E: Debug> TracedResult<T, E> {
     /// Equivalent to `std::result::Result::<T, TracedError<E>>::unwrap()`
     #[inline(always)]
+    #[track_caller]
     pub fn unwrap(self) -> T {
-        self.into_result().unwrap()
+        self.mark_in_flight().into_result().unwrap()
     }
 
     /// Equivalent to `std::result::Result::<T, TracedError<E>>::unwrap_err()`
     #[inline(always)]
+    #[track_caller]
     pub fn unwrap_err(self) -> TracedError<E> {
         self.into_result().unwrap_err()
     }
 
     /// Equivalent to `std::result::Result::<T, TracedError<E>>::expect()`
     #[inline(always)]
-    pub fn expect(self, msg: &'static str) -> T {
-        self.into_result().expect(msg)
+    #[track_caller]
+    pub fn expect(self, msg: &str) -> T {
+        self.mark_in_flight().into_result().expect(msg)
+    }
+
+    /// Like `expect()`, but the message is only built if this is an `Err`, e.g. `expect_with(|| format!("failed to load {path}"))`.
+    #[inline(always)]
+    #[track_caller]
+    pub fn expect_with(self, msg: impl FnOnce() -> String) -> T {
+        match self.mark_in_flight() {
+            TracedResult::Ok(ok) => ok,
+            TracedResult::Err(err) => panic!("{}: {err:?}", msg()),
+        }
+    }
+
+    /// If this is an `Err`, make its call stack available to the panic hook installed by `panic::install_hook()`.
+    #[inline(always)]
+    fn mark_in_flight(self) -> Self {
+        if let TracedResult::Err(err) = &self {
+            panic::set_in_flight(err.trace());
+        }
+        self
     }
 
     /// Equivalent to `std::result::Result::<T, TracedError<E>>::unwrap_unchecked()`
@@ -204,10 +1132,10 @@ impl<T: Debug, E: Debug> TracedResult<T, E> {
     }
 }
 
-impl<T, E> std::ops::Try for TracedResult<T, E> {
+impl<T, E, F: TraceFrame> std::ops::Try for TracedResult<T, E, F> {
     type Output = T;
 
-    type Residual = TracedResult<Infallible, E>;
+    type Residual = TracedResult<Infallible, E, F>;
 
     fn from_output(output: Self::Output) -> Self {
         TracedResult::Ok(output)
@@ -218,26 +1146,225 @@ impl<T, E> std::ops::Try for TracedResult<T, E> {
         match self {
             TracedResult::Ok(output) => ControlFlow::Continue(output),
             TracedResult::Err(mut error) => {
-                let branched_at = Location::caller();
-                error.trace.push(branched_at);
+                error.trace.record(FrameKind::Propagation);
                 ControlFlow::Break(TracedResult::Err(error))
             }
         }
     }
 }
 
-impl<T, R, E: From<R>> FromResidual<TracedResult<Infallible, R>> for TracedResult<T, E> {
-    fn from_residual(residual: TracedResult<Infallible, R>) -> Self {
+/// Allows generic code written against `Try` and `Residual` (like `Iterator::try_collect`) to produce a `TracedResult` with another `Ok` type.
+impl<T, E, F: TraceFrame> Residual<T> for TracedResult<Infallible, E, F> {
+    type TryType = TracedResult<T, E, F>;
+}
+
+/// If the error type changes, the location of the `?` operator is added to the call stack a second time as a `FrameKind::Conversion` frame.
+impl<T, R, E: From<R>, F: TraceFrame> FromResidual<TracedResult<Infallible, R, F>>
+    for TracedResult<T, E, F>
+{
+    #[track_caller]
+    fn from_residual(residual: TracedResult<Infallible, R, F>) -> Self {
         match residual {
-            TracedResult::Err(TracedError { trace, inner }) => TracedResult::Err(TracedError {
-                trace,
-                inner: From::from(inner),
-            }),
+            TracedResult::Err(err) => TracedResult::Err(err.convert()),
             _ => unreachable!(),
         }
     }
 }
 
+/// Allows using the `?` operator on a `std::result::Result` inside functions returning a `TracedResult`.
+/// The error is converted using `From` and wrapped in a new `TracedError` whose call stack starts at the location of the `?` operator.
+impl<T, R, E: From<R>, F: TraceFrame> FromResidual<Result<Infallible, R>>
+    for TracedResult<T, E, F>
+{
+    #[track_caller]
+    fn from_residual(residual: Result<Infallible, R>) -> Self {
+        match residual {
+            Err(err) => TracedResult::Err(TracedError::new_custom(From::from(err))),
+            Ok(never) => match never {},
+        }
+    }
+}
+
+/// Allows returning an error using `do yeet err` inside functions returning a `TracedResult`. The location of the `do yeet` expression becomes the origin of the error's call stack.
+impl<T, R, E: From<R>, F: TraceFrame> FromResidual<Yeet<R>> for TracedResult<T, E, F> {
+    #[track_caller]
+    fn from_residual(residual: Yeet<R>) -> Self {
+        TracedResult::Err(TracedError::new_custom(From::from(residual.0)))
+    }
+}
+
+/// Allows using the `?` operator on a `TracedResult` inside functions returning a `std::result::Result<T, TracedError<E>>`.
+/// The location of the `?` operator is still added to the error's call stack, but like with `into_result()`, the call stack freezes at that point.
+impl<T, R, E: From<R>, F: TraceFrame> FromResidual<TracedResult<Infallible, R, F>>
+    for Result<T, TracedError<E, F>>
+{
+    #[track_caller]
+    fn from_residual(residual: TracedResult<Infallible, R, F>) -> Self {
+        match residual {
+            TracedResult::Err(err) => Err(err.convert()),
+            TracedResult::Ok(never) => match never {},
+        }
+    }
+}
+
+/// The error produced when the `?` operator is used on a `None` value inside a function returning a `TracedResult`.
+/// To use `?` on `Option`s, the function's error type must implement `From<NoneError>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NoneError;
+
+impl std::fmt::Display for NoneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("value was None")
+    }
+}
+
+impl std::error::Error for NoneError {}
+
+/// Allows using the `?` operator on an `Option` inside functions returning a `TracedResult<T, E>` where `E: From<NoneError>`.
+/// The location of the `?` operator becomes the first entry in the resulting error's call stack.
+impl<T, E: From<NoneError>, F: TraceFrame> FromResidual<Option<Infallible>>
+    for TracedResult<T, E, F>
+{
+    #[track_caller]
+    fn from_residual(_: Option<Infallible>) -> Self {
+        TracedResult::Err(TracedError::new_custom(From::from(NoneError)))
+    }
+}
+
+/// Extension trait to continue tracking the call stack of a `Result<T, TracedError<E>>`, e.g. one obtained from `TracedResult::into_result()`.
+pub trait ResumeTrace<T, E> {
+    /// Convert this result back into a `TracedResult`, adding the caller location of this method to the error's call stack.
+    fn resume(self) -> TracedResult<T, E>;
+}
+
+impl<T, E> ResumeTrace<T, E> for Result<T, TracedError<E>> {
+    #[track_caller]
+    fn resume(self) -> TracedResult<T, E> {
+        match self {
+            Ok(ok) => TracedResult::Ok(ok),
+            Err(mut err) => {
+                err.trace.record(FrameKind::Propagation);
+                TracedResult::Err(err)
+            }
+        }
+    }
+}
+
+/// Extension trait to start tracing the call stack of a `std::result::Result`.
+pub trait IntoTraced<T, E> {
+    /// Convert this result into a `TracedResult`. If it is an `Err`, the caller location of this method becomes the first entry in the error's call stack.
+    fn traced(self) -> TracedResult<T, E>;
+}
+
+impl<T, E> IntoTraced<T, E> for Result<T, E> {
+    #[track_caller]
+    fn traced(self) -> TracedResult<T, E> {
+        match self {
+            Ok(ok) => TracedResult::Ok(ok),
+            Err(err) => TracedResult::Err(TracedError::new(err)),
+        }
+    }
+}
+
+/// Extension trait for fallible conversions using `TryFrom`.
+pub trait TryIntoTraced: Sized {
+    /// Equivalent to `U::try_from(self)`, but a failed conversion becomes a `TracedError` whose call stack starts at the caller location of this method: `let port: u16 = value.try_into_traced()?;`
+    fn try_into_traced<U: TryFrom<Self>>(self) -> TracedResult<U, U::Error>;
+}
+
+impl<T> TryIntoTraced for T {
+    #[track_caller]
+    fn try_into_traced<U: TryFrom<Self>>(self) -> TracedResult<U, U::Error> {
+        U::try_from(self).traced()
+    }
+}
+
+/// Extension trait to parse strings into traced results.
+pub trait ParseTraced {
+    /// Equivalent to `str::parse()`, but a parse error becomes a `TracedError` whose call stack starts at the caller location of this method: `"42".parse_traced::<u32>()`.
+    fn parse_traced<U: std::str::FromStr>(&self) -> TracedResult<U, U::Err>;
+}
+
+impl ParseTraced for str {
+    #[track_caller]
+    fn parse_traced<U: std::str::FromStr>(&self) -> TracedResult<U, U::Err> {
+        self.parse().traced()
+    }
+}
+
+/// Extension trait to convert an `Option` into a `TracedResult`.
+pub trait OkOrTraced<T> {
+    /// Equivalent to `Option::<T>::ok_or()`, but the caller location of this method becomes the first entry in the error's call stack.
+    fn ok_or_traced<E>(self, err: E) -> TracedResult<T, E>;
+
+    /// Equivalent to `Option::<T>::ok_or_else()`, but the caller location of this method becomes the first entry in the error's call stack.
+    fn ok_or_else_traced<E>(self, err: impl FnOnce() -> E) -> TracedResult<T, E>;
+}
+
+impl<T> OkOrTraced<T> for Option<T> {
+    #[track_caller]
+    fn ok_or_traced<E>(self, err: E) -> TracedResult<T, E> {
+        match self {
+            Some(some) => TracedResult::Ok(some),
+            None => TracedResult::Err(TracedError::new(err)),
+        }
+    }
+
+    #[track_caller]
+    fn ok_or_else_traced<E>(self, err: impl FnOnce() -> E) -> TracedResult<T, E> {
+        match self {
+            Some(some) => TracedResult::Ok(some),
+            None => TracedResult::Err(TracedError::new(err())),
+        }
+    }
+}
+
+/// Allows using the `?` operator on a `ControlFlow<TracedError<E>, _>` inside functions returning a `TracedResult`, adding the location of the `?` operator to the error's call stack.
+impl<T, E> FromResidual<ControlFlow<TracedError<E>, Infallible>> for TracedResult<T, E> {
+    #[track_caller]
+    fn from_residual(residual: ControlFlow<TracedError<E>, Infallible>) -> Self {
+        match residual {
+            ControlFlow::Break(mut err) => {
+                err.trace.record(FrameKind::Propagation);
+                TracedResult::Err(err)
+            }
+            ControlFlow::Continue(never) => match never {},
+        }
+    }
+}
+
+/// Allows using the `?` operator on a `TracedResult` inside functions returning a `ControlFlow<TracedError<E>, _>`.
+/// Like with `into_result()`, the call stack freezes at that point.
+impl<C, R, E: From<R>> FromResidual<TracedResult<Infallible, R>>
+    for ControlFlow<TracedError<E>, C>
+{
+    #[track_caller]
+    fn from_residual(residual: TracedResult<Infallible, R>) -> Self {
+        match residual {
+            TracedResult::Err(err) => ControlFlow::Break(err.convert()),
+            TracedResult::Ok(never) => match never {},
+        }
+    }
+}
+
+impl<T, E> From<TracedResult<T, E>> for ControlFlow<TracedError<E>, T> {
+    fn from(value: TracedResult<T, E>) -> Self {
+        match value {
+            TracedResult::Ok(ok) => ControlFlow::Continue(ok),
+            TracedResult::Err(err) => ControlFlow::Break(err),
+        }
+    }
+}
+
+impl<T, E> From<ControlFlow<TracedError<E>, T>> for TracedResult<T, E> {
+    fn from(value: ControlFlow<TracedError<E>, T>) -> Self {
+        match value {
+            ControlFlow::Continue(ok) => TracedResult::Ok(ok),
+            ControlFlow::Break(err) => TracedResult::Err(err),
+        }
+    }
+}
+
 impl<T, E> From<Result<T, E>> for TracedResult<T, E> {
     #[track_caller]
     fn from(value: Result<T, E>) -> Self {
@@ -0,0 +1,50 @@
+use std::{any::type_name, fmt::Display};
+
+use ::log::Level;
+
+use crate::{Severity, TracedError, TracedResult};
+
+impl Severity {
+    /// The level errors of this severity are logged at: `Warn` for warnings and `Error` otherwise.
+    pub fn log_level(self) -> Level {
+        match self {
+            Severity::Warning => Level::Warn,
+            Severity::Error | Severity::Fatal => Level::Error,
+        }
+    }
+}
+
+impl<E: Display> TracedError<E> {
+    /// Log this error at the specified level. The message contains the error and its call stack, while the type of the error, the file and line of its origin, the number of frames and its severity are attached as the key-value pairs `error_type`, `origin_file`, `origin_line`, `frame_count` and `severity`, so structured loggers can index them as fields. The message is formatted using `display_for_report`.
+    pub fn log(&self, level: Level) {
+        let origin = self.trace().first();
+        ::log::log!(
+            level,
+            error_type = type_name::<E>(),
+            origin_file = origin.map(|frame| frame.file()),
+            origin_line = origin.map(|frame| frame.line()),
+            frame_count = self.trace().len(),
+            severity = self.severity().unwrap_or_default().as_str();
+            "{}",
+            self.display_for_report()
+        );
+    }
+
+    /// Log this error at the level matching its severity (see `with_severity` and `Severity::log_level`).
+    pub fn log_by_severity(&self) {
+        self.log(self.severity().unwrap_or_default().log_level());
+    }
+}
+
+impl<T, E: Display> TracedResult<T, E> {
+    /// Return the `Ok` value, or log the error with its call stack at the specified level (see `TracedError::log`) and return `default`.
+    pub fn recover_logged(self, level: Level, default: T) -> T {
+        match self {
+            TracedResult::Ok(ok) => ok,
+            TracedResult::Err(err) => {
+                err.log(level);
+                default
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::fmt::{Display, Formatter, Result};
+
+use crate::{format::FormatOptions, FrameKind, Trace, TracedError};
+
+/// Two errors combined using `TracedError::merge`, each with its own call stack.
+/// Its `Display` implementation renders both errors as a tree, each formatted like a `TracedError` with its call stack, code, tags and help texts. Like any error message, the tree is only scrubbed when the merged error is formatted for a report (see `TracedError::display_for_report`).
+#[derive(Debug, Clone)]
+pub struct Merged<E, F> {
+    first: TracedError<E>,
+    second: TracedError<F>,
+}
+
+impl<E, F> Merged<E, F> {
+    pub fn first(&self) -> &TracedError<E> {
+        &self.first
+    }
+
+    pub fn second(&self) -> &TracedError<F> {
+        &self.second
+    }
+
+    pub fn into_parts(self) -> (TracedError<E>, TracedError<F>) {
+        (self.first, self.second)
+    }
+}
+
+impl<E> TracedError<E> {
+    /// Combine this error with another one, e.g. when both an operation and its fallback failed.
+    /// The returned error contains both errors along with their call stacks, and its own call stack starts at the caller location of this method.
+    #[track_caller]
+    pub fn merge<F>(self, other: TracedError<F>) -> TracedError<Merged<E, F>> {
+        TracedError {
+            trace: Trace::starting_at(FrameKind::Merge),
+            inner: Merged {
+                first: self,
+                second: other,
+            },
+            attachments: Default::default(),
+        }
+    }
+}
+
+fn write_branch<E: Display>(f: &mut Formatter<'_>, error: &TracedError<E>, last: bool) -> Result {
+    let (head, tail) = if last {
+        ("└─ ", "   ")
+    } else {
+        ("├─ ", "│  ")
+    };
+
+    // Colors are left to the formatter of the merged error, which shows the whole tree as its message
+    let branch = error
+        .display_with(FormatOptions::default().color(false))
+        .to_string();
+    for (index, line) in branch.lines().enumerate() {
+        let prefix = if index == 0 { head } else { tail };
+        writeln!(f, "{prefix}{line}")?;
+    }
+    Ok(())
+}
+
+impl<E: Display, F: Display> Display for Merged<E, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        writeln!(f, "Multiple errors occurred:")?;
+        write_branch(f, &self.first, false)?;
+        write_branch(f, &self.second, true)
+    }
+}
+
+impl<E, F> std::error::Error for Merged<E, F>
+where
+    E: std::error::Error,
+    F: std::error::Error,
+{
+}
@@ -0,0 +1,124 @@
+use std::{borrow::Cow, fmt::Display};
+
+use crate::TracedError;
+
+/// How severe an error is, see `TracedError::with_severity`. Integrations use it to pick a log level and to decide whether an error should alert someone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Something went wrong, but the application can continue normally, e.g. a failed request that is retried.
+    Warning,
+    /// An operation failed. Errors without a severity are treated as this.
+    #[default]
+    Error,
+    /// The application cannot continue, or a failure requires immediate attention.
+    Fatal,
+}
+
+impl Severity {
+    /// The lowercase name of this severity, e.g. `"warning"`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Fatal => "fatal",
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The attachment used to store an error code. See `TracedError::with_code`.
+#[derive(Debug, Clone, Copy)]
+struct ErrorCode(&'static str);
+
+/// The attachment used to store help texts. See `TracedError::with_help`.
+#[derive(Debug, Clone)]
+struct Help(Cow<'static, str>);
+
+/// The attachment used to store a tag. See `TracedError::tag`.
+#[derive(Debug, Clone)]
+struct Tag(Cow<'static, str>, Cow<'static, str>);
+
+/// The attachment used to store a documentation URL. See `TracedError::with_url`.
+#[derive(Debug, Clone)]
+struct Url(Cow<'static, str>);
+
+impl<E> TracedError<E> {
+    /// Set a code identifying the class of this error, e.g. `"E1042"`, independently of its message.
+    /// The code is shown in front of the message when the error is formatted, and included in exported reports.
+    pub fn with_code(self, code: &'static str) -> Self {
+        self.with_attachment(ErrorCode(code))
+    }
+
+    /// The code set using `with_code`, if any.
+    pub fn code(&self) -> Option<&'static str> {
+        self.get_attachment::<ErrorCode>().map(|code| code.0)
+    }
+
+    /// Set how severe this error is.
+    /// The `log`, `sentry`, `otel` and system log integrations use it to choose the level errors are logged at, and a `reporter::Reporter` writes `Fatal` errors immediately, bypassing its rate limit.
+    pub fn with_severity(self, severity: Severity) -> Self {
+        self.with_attachment(severity)
+    }
+
+    /// The severity set using `with_severity`, if any. Integrations treat errors without a severity as `Severity::Error`.
+    pub fn severity(&self) -> Option<Severity> {
+        self.get_attachment::<Severity>().copied()
+    }
+
+    /// Add a text telling users how to resolve this error, e.g. `"try running with --force"`. Can be called multiple times to add several texts.
+    /// Help texts are shown in their own section after the call stack when the error is formatted.
+    pub fn with_help(self, help: impl Into<Cow<'static, str>>) -> Self {
+        self.with_attachment(Help(help.into()))
+    }
+
+    /// The help texts added using `with_help`, in the order they were added.
+    pub fn help(&self) -> impl Iterator<Item = &str> {
+        self.get_attachments::<Help>().map(|help| help.0.as_ref())
+    }
+
+    /// Link this error to a page documenting it, e.g. a runbook for its error code.
+    /// The URL is shown after the call stack when the error is formatted, as a hyperlink if colors are enabled.
+    pub fn with_url(self, url: impl Into<Cow<'static, str>>) -> Self {
+        self.with_attachment(Url(url.into()))
+    }
+
+    /// The URL set using `with_url`, if any.
+    pub fn url(&self) -> Option<&str> {
+        self.get_attachment::<Url>().map(|url| url.0.as_ref())
+    }
+
+    /// Tag this error with a key-value pair, e.g. `tag("tenant", tenant_id)`, to tell apart errors of different tenants, requests or jobs. Tagging an error with the same key again replaces the value.
+    /// Tags are shown after the call stack when the error is formatted, and exported by the `sentry` and `otel` integrations and the `reporter`, so reports can be filtered by them.
+    pub fn tag(
+        self,
+        key: impl Into<Cow<'static, str>>,
+        value: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        self.with_attachment(Tag(key.into(), value.into()))
+    }
+
+    /// The tags of this error, in the order they were first added.
+    pub fn tags(&self) -> impl Iterator<Item = (&str, &str)> {
+        let mut tags: Vec<(&str, &str)> = Vec::new();
+        for Tag(key, value) in self.get_attachments::<Tag>() {
+            match tags.iter_mut().find(|(existing, _)| existing == key) {
+                Some(tag) => tag.1 = value,
+                None => tags.push((key, value)),
+            }
+        }
+        tags.into_iter()
+    }
+
+    /// The value of the tag `key`, if this error has one.
+    pub fn get_tag(&self, key: &str) -> Option<&str> {
+        self.get_attachments::<Tag>()
+            .rev()
+            .find(|tag| tag.0 == key)
+            .map(|tag| tag.1.as_ref())
+    }
+}
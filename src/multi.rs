@@ -0,0 +1,131 @@
+use std::fmt::{Debug, Display};
+
+use crate::{TracedError, TracedResult};
+
+/// A collection of `TracedError`s, each with its own call stack, for when more than one failure needs to be reported (e.g. validation or batch processing).
+#[derive(Debug, Clone)]
+pub struct MultiTracedError<E> {
+    errors: Vec<TracedError<E>>,
+}
+
+impl<E> MultiTracedError<E> {
+    pub fn new() -> Self {
+        Self { errors: Vec::new() }
+    }
+
+    pub fn push(&mut self, error: TracedError<E>) {
+        self.errors.push(error);
+    }
+
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn errors(&self) -> &[TracedError<E>] {
+        &self.errors
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, TracedError<E>> {
+        self.errors.iter()
+    }
+
+    pub fn into_errors(self) -> Vec<TracedError<E>> {
+        self.errors
+    }
+
+    /// Returns `Ok(())` if no errors were collected, or `Err(self)` otherwise.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl<T, E> TracedResult<T, E> {
+    /// Combine two results without short-circuiting: returns both values if both are `Ok`, or every error otherwise.
+    /// The call stack of the returned error starts at the caller location of this method.
+    #[track_caller]
+    pub fn zip_validate<U>(
+        self,
+        other: TracedResult<U, E>,
+    ) -> TracedResult<(T, U), MultiTracedError<E>> {
+        match (self, other) {
+            (TracedResult::Ok(first), TracedResult::Ok(second)) => {
+                TracedResult::Ok((first, second))
+            }
+            (TracedResult::Err(err), TracedResult::Ok(_))
+            | (TracedResult::Ok(_), TracedResult::Err(err)) => {
+                TracedResult::Err(TracedError::new(vec![err].into()))
+            }
+            (TracedResult::Err(first), TracedResult::Err(second)) => {
+                TracedResult::Err(TracedError::new(vec![first, second].into()))
+            }
+        }
+    }
+}
+
+impl<E> Default for MultiTracedError<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> From<Vec<TracedError<E>>> for MultiTracedError<E> {
+    fn from(errors: Vec<TracedError<E>>) -> Self {
+        Self { errors }
+    }
+}
+
+impl<E> FromIterator<TracedError<E>> for MultiTracedError<E> {
+    fn from_iter<I: IntoIterator<Item = TracedError<E>>>(iter: I) -> Self {
+        Self {
+            errors: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<E> Extend<TracedError<E>> for MultiTracedError<E> {
+    fn extend<I: IntoIterator<Item = TracedError<E>>>(&mut self, iter: I) {
+        self.errors.extend(iter);
+    }
+}
+
+impl<E> IntoIterator for MultiTracedError<E> {
+    type Item = TracedError<E>;
+    type IntoIter = std::vec::IntoIter<TracedError<E>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.into_iter()
+    }
+}
+
+impl<'a, E> IntoIterator for &'a MultiTracedError<E> {
+    type Item = &'a TracedError<E>;
+    type IntoIter = std::slice::Iter<'a, TracedError<E>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.errors.iter()
+    }
+}
+
+/// Formats the number of errors, followed by every error and its call stack.
+impl<E: Display> Display for MultiTracedError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.errors.len() {
+            1 => writeln!(f, "1 error occurred:")?,
+            count => writeln!(f, "{count} errors occurred:")?,
+        }
+        for (index, error) in self.errors.iter().enumerate() {
+            write!(f, "[{}] {error}", index + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: Debug + Display> std::error::Error for MultiTracedError<E> {}
@@ -0,0 +1,118 @@
+use std::{
+    convert::Infallible,
+    ops::{ControlFlow, FromResidual, Residual, Try},
+};
+
+use crate::{FrameKind, NoneError, TracedError, TracedResult};
+
+/// An optional value whose `None` variant records where it was created and where it was propagated, for cases where a missing value is a bug that needs to be located.
+/// Using `?` on a `TracedOption` inside a function returning a `TracedResult` converts the `None` into a traced error, keeping its call stack.
+#[derive(Debug, Clone)]
+#[must_use = "this `TracedOption` may be a `None` variant, which should be handled"]
+pub enum TracedOption<T> {
+    Some(T),
+    None(TracedError<NoneError>),
+}
+
+impl<T> TracedOption<T> {
+    /// Create a `None` whose call stack starts at the caller location of this method.
+    #[track_caller]
+    pub fn none() -> Self {
+        TracedOption::None(TracedError::new(NoneError))
+    }
+
+    pub fn is_some(&self) -> bool {
+        matches!(self, TracedOption::Some(_))
+    }
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, TracedOption::None(_))
+    }
+
+    /// Discard the call stack and convert into a regular `Option`.
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            TracedOption::Some(value) => Some(value),
+            TracedOption::None(_) => None,
+        }
+    }
+
+    /// Convert into a `TracedResult`, keeping the call stack of a `None`.
+    #[track_caller]
+    pub fn into_traced_result<E: From<NoneError>>(self) -> TracedResult<T, E> {
+        match self {
+            TracedOption::Some(value) => TracedResult::Ok(value),
+            TracedOption::None(err) => TracedResult::Err(err.convert()),
+        }
+    }
+}
+
+/// A `None` created by this conversion starts its call stack at the caller location.
+impl<T> From<Option<T>> for TracedOption<T> {
+    #[track_caller]
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => TracedOption::Some(value),
+            None => TracedOption::none(),
+        }
+    }
+}
+
+impl<T> From<TracedOption<T>> for Option<T> {
+    fn from(value: TracedOption<T>) -> Self {
+        value.into_option()
+    }
+}
+
+impl<T> Try for TracedOption<T> {
+    type Output = T;
+
+    type Residual = TracedOption<Infallible>;
+
+    fn from_output(output: Self::Output) -> Self {
+        TracedOption::Some(output)
+    }
+
+    #[track_caller]
+    fn branch(self) -> ControlFlow<Self::Residual, Self::Output> {
+        match self {
+            TracedOption::Some(value) => ControlFlow::Continue(value),
+            TracedOption::None(mut err) => {
+                err.trace.record(FrameKind::Propagation);
+                ControlFlow::Break(TracedOption::None(err))
+            }
+        }
+    }
+}
+
+impl<T> Residual<T> for TracedOption<Infallible> {
+    type TryType = TracedOption<T>;
+}
+
+impl<T> FromResidual<TracedOption<Infallible>> for TracedOption<T> {
+    fn from_residual(residual: TracedOption<Infallible>) -> Self {
+        match residual {
+            TracedOption::None(err) => TracedOption::None(err),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// The `None` starts its call stack at the location of the `?` operator.
+impl<T> FromResidual<Option<Infallible>> for TracedOption<T> {
+    #[track_caller]
+    fn from_residual(_: Option<Infallible>) -> Self {
+        TracedOption::none()
+    }
+}
+
+/// If `E` is not `NoneError`, the location of the `?` operator is added to the call stack a second time as a `FrameKind::Conversion` frame.
+impl<T, E: From<NoneError>> FromResidual<TracedOption<Infallible>> for TracedResult<T, E> {
+    #[track_caller]
+    fn from_residual(residual: TracedOption<Infallible>) -> Self {
+        match residual {
+            TracedOption::None(err) => TracedResult::Err(err.convert()),
+            _ => unreachable!(),
+        }
+    }
+}
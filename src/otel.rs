@@ -0,0 +1,67 @@
+use std::{any::type_name, fmt::Display};
+
+use opentelemetry::{
+    trace::{get_active_span, SpanRef, Status},
+    Array, KeyValue, StringValue, Value,
+};
+
+use crate::{build_info::build_info, format::scrub, TracedError};
+
+impl<E: Display> TracedError<E> {
+    /// Record this error on `span` as an `exception` event and set the span's status to `Error`.
+    /// Besides the usual `exception.type`, `exception.message` and `exception.stacktrace` attributes, the event carries the call stack as a string array in `exception.frames`, origin first, and its code and severity (if set) in `exception.code` and `exception.severity`. Each tag (see `tag`) becomes an `exception.tag.<key>` attribute.
+    /// If build info was installed using `build_info::set_build_info`, it is added as `build.*` attributes.
+    /// The message and tags are passed through the `Scrubber` installed with `Config::scrubber`.
+    pub fn record_on_span(&self, span: &SpanRef<'_>) {
+        let frames: Vec<StringValue> = self
+            .trace()
+            .iter()
+            .map(|location| location.to_string().into())
+            .collect();
+        let stacktrace = frames
+            .iter()
+            .map(StringValue::as_str)
+            .collect::<Vec<_>>()
+            .join("\n");
+        let message = self.scrubbed_message();
+
+        let mut attributes = vec![
+            KeyValue::new("exception.type", type_name::<E>()),
+            KeyValue::new("exception.message", message.clone()),
+            KeyValue::new("exception.stacktrace", stacktrace),
+            KeyValue::new("exception.frames", Value::Array(Array::String(frames))),
+        ];
+        if let Some(code) = self.code() {
+            attributes.push(KeyValue::new("exception.code", code));
+        }
+        if let Some(severity) = self.severity() {
+            attributes.push(KeyValue::new("exception.severity", severity.as_str()));
+        }
+        for (key, value) in self.tags() {
+            attributes.push(KeyValue::new(
+                format!("exception.tag.{key}"),
+                scrub(value).into_owned(),
+            ));
+        }
+        if let Some(info) = build_info() {
+            attributes.push(KeyValue::new("build.crate_name", info.crate_name));
+            attributes.push(KeyValue::new("build.crate_version", info.crate_version));
+            if let Some(git_hash) = info.git_hash {
+                attributes.push(KeyValue::new("build.git_hash", git_hash));
+            }
+            attributes.push(KeyValue::new("build.profile", info.profile));
+            attributes.push(KeyValue::new(
+                "build.target",
+                format!("{}-{}", info.target_arch, info.target_os),
+            ));
+        }
+
+        span.add_event("exception", attributes);
+        span.set_status(Status::error(message));
+    }
+
+    /// Record this error on the currently active span. See `record_on_span`.
+    pub fn record_on_current_span(&self) {
+        get_active_span(|span| self.record_on_span(&span))
+    }
+}
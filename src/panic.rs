@@ -0,0 +1,155 @@
+use std::{
+    any::Any,
+    backtrace::{Backtrace, BacktraceStatus},
+    cell::{Cell, RefCell},
+    fmt::Write,
+    panic::PanicHookInfo,
+    sync::Once,
+};
+
+use crate::{
+    crash::{announce, CrashReport},
+    format::{scrub, FormatOptions},
+    Config, Frame,
+};
+
+thread_local! {
+    /// The call stack of the traced error that is about to cause a panic on this thread, if any.
+    static IN_FLIGHT: RefCell<Option<Vec<Frame>>> = const { RefCell::new(None) };
+    /// The number of hooks of this module currently running on this thread.
+    static HOOK_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Remember the call stack of an error that is about to be unwrapped, so the panic hook can print it.
+pub(crate) fn set_in_flight(trace: &[Frame]) {
+    IN_FLIGHT.with_borrow_mut(|in_flight| *in_flight = Some(trace.to_vec()));
+}
+
+/// Marks a hook of this module as running. Hooks installed on top of each other all see the call stack of the unwrapped error; it is cleared once the outermost one returns.
+struct HookScope;
+
+impl HookScope {
+    fn enter() -> Self {
+        HOOK_DEPTH.set(HOOK_DEPTH.get() + 1);
+        HookScope
+    }
+
+    /// The call stack of the traced error that caused the current panic, if any.
+    fn in_flight(&self) -> Option<Vec<Frame>> {
+        IN_FLIGHT.with_borrow(Clone::clone)
+    }
+}
+
+impl Drop for HookScope {
+    fn drop(&mut self) {
+        let depth = HOOK_DEPTH.get() - 1;
+        HOOK_DEPTH.set(depth);
+        if depth == 0 {
+            IN_FLIGHT.with_borrow_mut(|in_flight| *in_flight = None);
+        }
+    }
+}
+
+/// Install a panic hook that, after running the previously installed hook, prints the call stack of the traced error that caused the panic if it was caused by `unwrap()` or `expect()` on a `TracedResult`.
+/// Calling this more than once has no effect. It can be combined with `install_report_hook` and `install_crash_report_hook`.
+pub fn install_hook() {
+    static INSTALLED: Once = Once::new();
+    INSTALLED.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let scope = HookScope::enter();
+            previous(info);
+            if let Some(trace) = scope.in_flight() {
+                let options = FormatOptions::default();
+                let mut message = String::from("Call stack of the unwrapped error:\n");
+                for frame in options.ordered(&trace) {
+                    let _ = options.write_frame(&mut message, frame);
+                }
+                eprint!("{message}");
+            }
+        }));
+    });
+}
+
+/// Install a panic hook that, for panics caused by `unwrap()` or `expect()` on a `TracedResult`, prints a single report with sections for the panic message and location, the call stack of the traced error and the backtrace, similar to `color-eyre`.
+/// Other panics are still handled by the previously installed hook. Calling this more than once has no effect. It can be combined with `install_hook` and `install_crash_report_hook`.
+pub fn install_report_hook() {
+    static INSTALLED: Once = Once::new();
+    INSTALLED.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let scope = HookScope::enter();
+            match scope.in_flight() {
+                Some(trace) => eprint!("{}", report(info, &trace)),
+                None => previous(info),
+            }
+        }));
+    });
+}
+
+/// Install a panic hook that, after running the previously installed hook, writes a crash report file for every panic (see `crash::write_crash_report`) and tells the user where to find it, like `human-panic`.
+/// For panics caused by `unwrap()` or `expect()` on a `TracedResult`, the report includes the call stack of the traced error. Calling this more than once has no effect. It can be combined with `install_hook` and `install_report_hook`.
+pub fn install_crash_report_hook() {
+    static INSTALLED: Once = Once::new();
+    INSTALLED.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let scope = HookScope::enter();
+            previous(info);
+            let trace = scope.in_flight().unwrap_or_default();
+            let message = payload_message(info.payload()).unwrap_or("<non-string panic payload>");
+            let report = CrashReport {
+                message: scrub(message).into_owned(),
+                location: info.location().map(ToString::to_string),
+                details: Vec::new(),
+                frames: &trace,
+            };
+            announce(report.write());
+        }));
+    });
+}
+
+/// The report printed by the hook installed using `install_report_hook`.
+fn report(info: &PanicHookInfo<'_>, trace: &[Frame]) -> String {
+    let options = FormatOptions::default();
+    let color = Config::current().color.enabled();
+    let mut report = String::from("The application panicked (crashed).\n");
+
+    let message = payload_message(info.payload()).unwrap_or("<non-string panic payload>");
+    let _ = writeln!(report, "Message:  {message}");
+    if let Some(location) = info.location() {
+        let _ = writeln!(report, "Location: {location}");
+    }
+
+    write_section(&mut report, "TRACED ERROR", color);
+    for frame in options.ordered(trace) {
+        let _ = options.write_frame(&mut report, frame);
+    }
+
+    write_section(&mut report, "BACKTRACE", color);
+    let backtrace = Backtrace::capture();
+    if backtrace.status() == BacktraceStatus::Captured {
+        let _ = writeln!(report, "{backtrace}");
+    } else {
+        report.push_str("Run with RUST_BACKTRACE=1 to include a backtrace of the panic.\n");
+    }
+    report
+}
+
+/// The message of a panic, if its payload is a string as created by `panic!`.
+pub(crate) fn payload_message(payload: &(dyn Any + Send)) -> Option<&str> {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+}
+
+/// Write a header separating the sections of a panic report.
+fn write_section(report: &mut String, title: &str, color: bool) {
+    let rule = "━".repeat(20);
+    if color {
+        let _ = writeln!(report, "\n{rule} \x1b[1;35m{title}\x1b[0m {rule}");
+    } else {
+        let _ = writeln!(report, "\n{rule} {title} {rule}");
+    }
+}
@@ -0,0 +1,36 @@
+use std::cell::RefCell;
+
+use crate::Frame;
+
+/// The maximum number of buffers kept per thread.
+const MAX_POOLED: usize = 32;
+
+/// Buffers that grew larger than this are freed instead of pooled, so a single deep call stack doesn't keep its memory alive.
+const MAX_CAPACITY: usize = 64;
+
+thread_local! {
+    static POOL: RefCell<Vec<Vec<Frame>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// An empty buffer for a call stack, reusing one freed on this thread if possible.
+pub(crate) fn take() -> Vec<Frame> {
+    POOL.try_with(|pool| pool.borrow_mut().pop())
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// Return a buffer that is no longer needed to the pool of this thread.
+pub(crate) fn recycle(mut frames: Vec<Frame>) {
+    if frames.capacity() == 0 || frames.capacity() > MAX_CAPACITY {
+        return;
+    }
+    frames.clear();
+    // The pool is unavailable while the thread's locals are destroyed
+    let _ = POOL.try_with(|pool| {
+        let mut pool = pool.borrow_mut();
+        if pool.len() < MAX_POOLED {
+            pool.push(frames);
+        }
+    });
+}
@@ -0,0 +1,321 @@
+use std::{
+    fmt::Display,
+    io,
+    path::Path,
+    process::{Command, ExitStatus, Output},
+    sync::Arc,
+};
+
+use crate::{
+    wire::{WireFrame, WireFrameKind},
+    FrameKind, HttpStatus, Trace, TracedError, TracedResult,
+};
+
+/// The environment variable a parent process can set to the path of a file that `report_to_parent` should write its report to.
+/// On Unix, this can also be a path like `/dev/fd/3` to use an inherited file descriptor.
+pub const REPORT_PATH_ENV: &str = "TRACED_RESULT_REPORT_PATH";
+
+/// Marks the lines of a report written to stderr by `report_to_parent`.
+pub const STDERR_SENTINEL: &str = "@@traced_result@@";
+
+/// An error reassembled from a report, e.g. one written by a child process using `report_to_parent`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChildError {
+    message: String,
+    remote_trace: Vec<WireFrame>,
+}
+
+impl ChildError {
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The frames recorded by the other process, in the order they were recorded.
+    pub fn remote_trace(&self) -> &[WireFrame] {
+        &self.remote_trace
+    }
+}
+
+impl Display for ChildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ChildError {}
+
+/// Why a command run using `CommandExt` failed.
+#[derive(Debug)]
+pub enum CommandFailure {
+    /// The command could not be started, e.g. because the program does not exist.
+    Spawn(io::Error),
+    /// The command exited with an unsuccessful status.
+    Exit(ExitStatus),
+}
+
+/// The error returned by `CommandExt`, including the command line and why it failed.
+#[derive(Debug)]
+pub struct CommandError {
+    command: String,
+    failure: CommandFailure,
+    stderr: Option<String>,
+}
+
+impl CommandError {
+    /// The program and its arguments. Arguments containing whitespace or quotes are quoted.
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    pub fn failure(&self) -> &CommandFailure {
+        &self.failure
+    }
+
+    /// The exit status of the command, if it was started.
+    pub fn status(&self) -> Option<ExitStatus> {
+        match self.failure {
+            CommandFailure::Spawn(_) => None,
+            CommandFailure::Exit(status) => Some(status),
+        }
+    }
+
+    /// What the command wrote to stderr, if it was captured using `CommandExt::output_traced`.
+    pub fn stderr(&self) -> Option<&str> {
+        self.stderr.as_deref()
+    }
+}
+
+impl Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.failure {
+            CommandFailure::Spawn(err) => write!(f, "failed to run `{}`: {err}", self.command)?,
+            CommandFailure::Exit(status) => write!(f, "`{}` failed with {status}", self.command)?,
+        }
+        match self.stderr.as_deref().map(str::trim_end) {
+            Some(stderr) if !stderr.is_empty() => write!(f, "\n{stderr}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.failure {
+            CommandFailure::Spawn(err) => Some(err),
+            CommandFailure::Exit(_) => None,
+        }
+    }
+}
+
+impl HttpStatus for CommandError {}
+
+/// Extension trait to run a `std::process::Command` and get a `TracedError` if it fails.
+pub trait CommandExt {
+    /// Run the command, waiting for it to finish. Fails if it cannot be started or exits unsuccessfully, with a call stack starting at the caller location of this method.
+    fn run_traced(&mut self) -> TracedResult<(), CommandError>;
+
+    /// Run the command and collect its output like `Command::output`. Fails like `run_traced`; the error then also includes what the command wrote to stderr.
+    fn output_traced(&mut self) -> TracedResult<Output, CommandError>;
+}
+
+impl CommandExt for Command {
+    #[track_caller]
+    fn run_traced(&mut self) -> TracedResult<(), CommandError> {
+        let failure = match self.status() {
+            Ok(status) if status.success() => return TracedResult::Ok(()),
+            Ok(status) => CommandFailure::Exit(status),
+            Err(err) => CommandFailure::Spawn(err),
+        };
+        TracedResult::Err(TracedError::new(CommandError {
+            command: command_line(self),
+            failure,
+            stderr: None,
+        }))
+    }
+
+    #[track_caller]
+    fn output_traced(&mut self) -> TracedResult<Output, CommandError> {
+        let (failure, stderr) = match self.output() {
+            Ok(output) if output.status.success() => return TracedResult::Ok(output),
+            Ok(output) => (
+                CommandFailure::Exit(output.status),
+                Some(String::from_utf8_lossy(&output.stderr).into_owned()),
+            ),
+            Err(err) => (CommandFailure::Spawn(err), None),
+        };
+        TracedResult::Err(TracedError::new(CommandError {
+            command: command_line(self),
+            failure,
+            stderr,
+        }))
+    }
+}
+
+/// The program and arguments of `command`, separated by spaces.
+fn command_line(command: &Command) -> String {
+    std::iter::once(command.get_program())
+        .chain(command.get_args())
+        .map(|arg| {
+            let arg = arg.to_string_lossy();
+            if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '"') {
+                format!("{arg:?}")
+            } else {
+                arg.into_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+impl<E: Display> TracedError<E> {
+    /// Serialize this error's message and call stack into a text report that can be turned back into a `TracedError` by `parse_report`, e.g. in another process.
+    /// The message is passed through the `Scrubber` installed with `Config::scrubber`.
+    pub fn to_process_report(&self) -> String {
+        let mut report = format!("message\t{}\n", escape(&self.scrubbed_message()));
+        for frame in self.trace() {
+            let kind = match frame.kind() {
+                FrameKind::Origin => "origin".to_owned(),
+                FrameKind::Propagation => "propagation".to_owned(),
+                FrameKind::Conversion { from, to } => format!("conversion\t{from}\t{to}"),
+                FrameKind::Merge => "merge".to_owned(),
+                FrameKind::Retry { attempt } => format!("retry\t{attempt}"),
+                FrameKind::ProcessBoundary => "boundary".to_owned(),
+            };
+            report.push_str(&format!("frame\t{}\t{}", frame.file(), frame.line()));
+            if !cfg!(feature = "no-columns") {
+                report.push_str(&format!("\t{}", frame.column()));
+            }
+            report.push_str(&format!("\t{kind}\n"));
+        }
+        report
+    }
+}
+
+/// Report `error` to the parent process: to the file named by the `REPORT_PATH_ENV` environment variable if it is set, or to stderr otherwise, with every line prefixed by `STDERR_SENTINEL`.
+pub fn report_to_parent<E: Display>(error: &TracedError<E>) -> io::Result<()> {
+    let report = error.to_process_report();
+    match std::env::var_os(REPORT_PATH_ENV) {
+        Some(path) => std::fs::write(path, report),
+        None => {
+            let report: String = report
+                .lines()
+                .map(|line| format!("{STDERR_SENTINEL}{line}\n"))
+                .collect();
+            io::Write::write_all(&mut io::stderr().lock(), report.as_bytes())
+        }
+    }
+}
+
+/// Reassemble an error from a report created by `TracedError::to_process_report`. Returns `None` if `report` is not a valid report.
+/// The frames recorded by the other process are available through `ChildError::remote_trace`. They own their strings, so parsing reports from untrusted processes doesn't leak memory. The call stack of the returned error starts with a `FrameKind::ProcessBoundary` frame at the caller location of this function.
+#[track_caller]
+pub fn parse_report(report: &str) -> Option<TracedError<ChildError>> {
+    let mut error = parse_report_frames(report)?;
+    error.trace.record(FrameKind::ProcessBoundary);
+    Some(error)
+}
+
+/// Reassemble an error from a report without adding a frame of its own.
+pub(crate) fn parse_report_frames(report: &str) -> Option<TracedError<ChildError>> {
+    let mut lines = report.lines();
+    let message = unescape(lines.next()?.strip_prefix("message\t")?);
+
+    let mut files: Vec<Arc<str>> = Vec::new();
+    let remote_trace = lines
+        .map(|line| parse_frame(line, &mut files))
+        .collect::<Option<Vec<_>>>()?;
+    Some(TracedError {
+        trace: Trace::new(Vec::new()),
+        inner: ChildError {
+            message,
+            remote_trace,
+        },
+        attachments: Default::default(),
+    })
+}
+
+/// Find a report written to stderr by `report_to_parent` in a child process' stderr output, and reassemble it using `parse_report`.
+#[track_caller]
+pub fn parse_stderr(stderr: &str) -> Option<TracedError<ChildError>> {
+    let report: String = stderr
+        .lines()
+        .filter_map(|line| line.strip_prefix(STDERR_SENTINEL))
+        .map(|line| format!("{line}\n"))
+        .collect();
+    parse_report(&report)
+}
+
+/// Read a report written by `report_to_parent` to the file at `path` (see `REPORT_PATH_ENV`), and reassemble it using `parse_report`.
+/// Returns `Ok(None)` if the file does not exist or does not contain a valid report.
+#[track_caller]
+pub fn read_report(path: impl AsRef<Path>) -> io::Result<Option<TracedError<ChildError>>> {
+    match std::fs::read_to_string(path) {
+        Ok(report) => Ok(parse_report(&report)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn parse_frame(line: &str, files: &mut Vec<Arc<str>>) -> Option<WireFrame> {
+    let mut fields = line.strip_prefix("frame\t")?.split('\t');
+    // Frames of the same call stack usually share files, so each one is only allocated once
+    let file = fields.next()?;
+    let file = match files.iter().find(|known| ***known == *file) {
+        Some(known) => known.clone(),
+        None => {
+            let file = Arc::from(file);
+            files.push(Arc::clone(&file));
+            file
+        }
+    };
+    let line = fields.next()?.parse().ok()?;
+    // Processes built with the `no-columns` feature don't report columns
+    let mut field = fields.next()?;
+    let column = match field.parse() {
+        Ok(column) => {
+            field = fields.next()?;
+            column
+        }
+        Err(_) => 0,
+    };
+    let kind = match field {
+        "origin" => WireFrameKind::Origin,
+        "propagation" => WireFrameKind::Propagation,
+        "conversion" => WireFrameKind::Conversion {
+            from: Arc::from(fields.next()?),
+            to: Arc::from(fields.next()?),
+        },
+        "merge" => WireFrameKind::Merge,
+        "retry" => WireFrameKind::Retry {
+            attempt: fields.next()?.parse().ok()?,
+        },
+        "boundary" => WireFrameKind::ProcessBoundary,
+        _ => return None,
+    };
+    Some(WireFrame::new(file, line, column, kind))
+}
+
+fn escape(message: &str) -> String {
+    message
+        .replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(message: &str) -> String {
+    let mut unescaped = String::with_capacity(message.len());
+    let mut chars = message.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => unescaped.push('\n'),
+            ('\\', Some('r')) => unescaped.push('\r'),
+            ('\\', Some('\\')) => unescaped.push('\\'),
+            _ => {
+                unescaped.push(c);
+                continue;
+            }
+        }
+        chars.next();
+    }
+    unescaped
+}
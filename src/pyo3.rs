@@ -0,0 +1,40 @@
+use std::fmt::Display;
+
+use ::pyo3::{exceptions::PyRuntimeError, types::PyAnyMethods, PyErr, Python};
+
+use crate::TracedError;
+
+/// Converts the error into a `RuntimeError` carrying its call stack. The message is passed through the `Scrubber` installed with `Config::scrubber`.
+/// The frames are stored as a list of `(file, line, column)` tuples (`(file, line)` with the `no-columns` feature) in the exception's `traced_frames` attribute and, on Python 3.11 and later, added as exception notes so they show up in Python tracebacks.
+impl<E: Display> From<TracedError<E>> for PyErr {
+    fn from(error: TracedError<E>) -> Self {
+        let err = PyRuntimeError::new_err(error.scrubbed_message());
+        Python::attach(|py| {
+            let value = err.value(py);
+            #[cfg(not(feature = "no-columns"))]
+            let frames: Vec<(&str, u32, u32)> = error
+                .trace()
+                .iter()
+                .map(|location| (location.file(), location.line(), location.column()))
+                .collect();
+            #[cfg(feature = "no-columns")]
+            let frames: Vec<(&str, u32)> = error
+                .trace()
+                .iter()
+                .map(|location| (location.file(), location.line()))
+                .collect();
+            let _ = value.setattr("traced_frames", frames);
+            // `add_note` only exists since Python 3.11; older versions still get the `traced_frames` attribute
+            for location in error.trace().iter().rev() {
+                let position = if cfg!(feature = "no-columns") {
+                    location.line().to_string()
+                } else {
+                    format!("{}:{}", location.line(), location.column())
+                };
+                let note = format!("At ({position}) in {file}", file = location.file());
+                let _ = value.call_method1("add_note", (note,));
+            }
+        });
+        err
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Mutex;
+
+use ::rayon::iter::{
+    Either, FromParallelIterator, IntoParallelIterator, ParallelExtend, ParallelIterator,
+};
+
+use crate::{MultiTracedError, TracedError, TracedResult};
+
+/// Collect a parallel iterator of `TracedResult`s, stopping at the first `Err` encountered. Equivalent to the `FromParallelIterator` implementation of `std::result::Result`.
+/// Since items are processed in parallel, the returned error is not necessarily the one that comes first in the iterator's order.
+impl<C, T, E> FromParallelIterator<TracedResult<T, E>> for TracedResult<C, E>
+where
+    C: FromParallelIterator<T>,
+    T: Send,
+    E: Send,
+{
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = TracedResult<T, E>>,
+    {
+        let first_error = Mutex::new(None);
+        let collection = par_iter
+            .into_par_iter()
+            .map(|item| match item {
+                TracedResult::Ok(ok) => Some(ok),
+                TracedResult::Err(err) => {
+                    if let Ok(mut first_error) = first_error.lock() {
+                        first_error.get_or_insert(err);
+                    }
+                    None
+                }
+            })
+            .while_some()
+            .collect();
+
+        match first_error.into_inner() {
+            Ok(Some(err)) => TracedResult::Err(err),
+            _ => TracedResult::Ok(collection),
+        }
+    }
+}
+
+/// Extension trait for parallel iterators over `TracedResult`s.
+pub trait ParallelTracedExt<T: Send, E: Send>: ParallelIterator<Item = TracedResult<T, E>> {
+    /// Process every item and collect the `Ok` values, or, if any item is an `Err`, all errors along with their individual call stacks.
+    /// The caller location of this method becomes the first entry in the returned error's call stack.
+    #[track_caller]
+    fn collect_all_traced<C>(self) -> TracedResult<C, MultiTracedError<E>>
+    where
+        C: Default + Send + ParallelExtend<T>,
+    {
+        let (collection, errors): (C, Vec<TracedError<E>>) =
+            self.partition_map(|item| match item {
+                TracedResult::Ok(ok) => Either::Left(ok),
+                TracedResult::Err(err) => Either::Right(err),
+            });
+
+        if errors.is_empty() {
+            TracedResult::Ok(collection)
+        } else {
+            TracedResult::Err(TracedError::new(errors.into()))
+        }
+    }
+}
+
+impl<I, T, E> ParallelTracedExt<T, E> for I
+where
+    I: ParallelIterator<Item = TracedResult<T, E>>,
+    T: Send,
+    E: Send,
+{
+}
@@ -0,0 +1,561 @@
+use std::{
+    any::type_name,
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    future::Future,
+    io,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex, MutexGuard},
+    task::{Context, Poll, Waker},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use crate::{format::scrub, wire::WireFrame, Severity, TracedError};
+
+/// An owned snapshot of a traced error, as queued by a `Reporter` and passed to its sinks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorReport {
+    message: String,
+    type_name: &'static str,
+    code: Option<&'static str>,
+    severity: Severity,
+    tags: Vec<(String, String)>,
+    fingerprint: u64,
+    frames: Vec<WireFrame>,
+    suppressed: u64,
+}
+
+impl ErrorReport {
+    pub fn new<E: Display>(error: &TracedError<E>) -> Self {
+        Self {
+            message: error.scrubbed_message(),
+            type_name: type_name::<E>(),
+            code: error.code(),
+            severity: error.severity().unwrap_or_default(),
+            tags: error
+                .tags()
+                .map(|(key, value)| (key.to_owned(), scrub(value).into_owned()))
+                .collect(),
+            fingerprint: error.fingerprint(),
+            frames: error.trace().iter().map(WireFrame::from).collect(),
+            suppressed: 0,
+        }
+    }
+
+    /// The `Display` output of the error, without its call stack, passed through the `Scrubber` installed with `Config::scrubber`.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The name of the error's type, as returned by `std::any::type_name`.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// The error's code, if one was set using `TracedError::with_code`.
+    pub fn code(&self) -> Option<&'static str> {
+        self.code
+    }
+
+    /// The error's severity, or `Severity::Error` if none was set using `TracedError::with_severity`.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// The error's tags, see `TracedError::tag`.
+    pub fn tags(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.tags
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// The error's fingerprint. See `TracedError::fingerprint`.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// The error's call stack, starting at its origin.
+    pub fn frames(&self) -> &[WireFrame] {
+        &self.frames
+    }
+
+    /// The number of reports with the same fingerprint that were dropped by the rate limit of the reporter (see `ReporterBuilder::rate_limit`).
+    /// Once a rate limit window in which reports were dropped ends, the last dropped report is passed to the sinks with this count as a summary; all other reports have a count of `0`.
+    pub fn suppressed(&self) -> u64 {
+        self.suppressed
+    }
+
+    /// Add a tag to this report, e.g. with context only known where the report is created.
+    #[cfg(feature = "tracing-subscriber")]
+    pub(crate) fn tagged(mut self, key: &str, value: &str) -> Self {
+        self.tags.push((key.to_owned(), scrub(value).into_owned()));
+        self
+    }
+
+    /// Put `frames`, recorded by another process before the error reached this one, in front of the call stack.
+    #[cfg(feature = "tracing-subscriber")]
+    pub(crate) fn with_remote_frames(mut self, frames: &[WireFrame]) -> Self {
+        self.frames.splice(0..0, frames.iter().cloned());
+        self
+    }
+}
+
+/// Formats the message of the report.
+impl Display for ErrorReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for ErrorReport {}
+
+/// A destination for the reports of a `Reporter`, e.g. a log file or a connection to a collector.
+/// Sinks are called on the reporter's background thread, so they may block.
+pub trait Sink: Send + 'static {
+    /// Write a batch of reports, in the order they were queued.
+    fn write_batch(&mut self, batch: &[ErrorReport]);
+
+    /// Flush any reports buffered by the sink itself. Called by `Reporter::flush` and when the reporter shuts down.
+    fn flush(&mut self) {}
+}
+
+impl<F: FnMut(&[ErrorReport]) + Send + 'static> Sink for F {
+    fn write_batch(&mut self, batch: &[ErrorReport]) {
+        self(batch)
+    }
+}
+
+/// Configures and starts a `Reporter`. See `Reporter::builder`.
+pub struct ReporterBuilder {
+    sinks: Vec<Box<dyn Sink>>,
+    batch_size: usize,
+    capacity: usize,
+    flush_interval: Duration,
+    rate_limit: Option<RateLimit>,
+}
+
+impl ReporterBuilder {
+    /// Add a sink that receives every batch of reports.
+    pub fn sink(mut self, sink: impl Sink) -> Self {
+        self.sinks.push(Box::new(sink));
+        self
+    }
+
+    /// The maximum number of reports passed to sinks at once. Defaults to 64.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// The number of reports that can be queued before `Reporter::report` blocks (and `try_report` drops reports) until the sinks caught up. Defaults to 1024.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// How long queued reports wait for a batch to fill up before they are written anyway. Defaults to one second.
+    pub fn flush_interval(mut self, interval: Duration) -> Self {
+        self.flush_interval = interval;
+        self
+    }
+
+    /// Report at most `max` errors with the same fingerprint (see `TracedError::fingerprint`) per `window`, so the sinks aren't flooded when the same error occurs many times per second. Errors with the severity `Fatal` are always reported.
+    /// The number of dropped reports is passed to the sinks once the window ends. See `ErrorReport::suppressed`.
+    pub fn rate_limit(mut self, max: u32, window: Duration) -> Self {
+        self.rate_limit = Some(RateLimit { max, window });
+        self
+    }
+
+    /// Start the reporter's background thread.
+    pub fn spawn(self) -> io::Result<Reporter> {
+        let shared = Arc::new(Shared {
+            state: Default::default(),
+            changed: Default::default(),
+            rate_limit: self.rate_limit,
+        });
+        let worker = Worker {
+            shared: shared.clone(),
+            sinks: self.sinks,
+            batch_size: self.batch_size,
+            flush_interval: self.flush_interval,
+        };
+        let thread = std::thread::Builder::new()
+            .name("traced-result-reporter".to_owned())
+            .spawn(move || worker.run())?;
+        Ok(Reporter {
+            shared,
+            capacity: self.capacity,
+            thread: Some(thread),
+        })
+    }
+}
+
+/// Queues traced errors and writes them to its sinks in batches on a background thread, so reporting errors doesn't block the code producing them.
+/// The queue is bounded: when it is full, `report` blocks and `report_async` waits until there is room again, while `try_report` drops the report.
+/// Dropping the reporter (or calling `shutdown`) writes every queued report before stopping the background thread. Once the background thread stopped, new reports are dropped.
+pub struct Reporter {
+    shared: Arc<Shared>,
+    capacity: usize,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Reporter {
+    pub fn builder() -> ReporterBuilder {
+        ReporterBuilder {
+            sinks: Vec::new(),
+            batch_size: 64,
+            capacity: 1024,
+            flush_interval: Duration::from_secs(1),
+            rate_limit: None,
+        }
+    }
+
+    /// Queue `error`, waiting for room in the queue if it is full.
+    pub fn report<E: Display>(&self, error: &TracedError<E>) {
+        let mut state = self.shared.lock();
+        if state.stopped {
+            return;
+        }
+        let Some(report) = self.shared.admit(&mut state, ErrorReport::new(error)) else {
+            return;
+        };
+        while state.queue.len() >= self.capacity && !state.stopped {
+            state = self
+                .shared
+                .changed
+                .wait(state)
+                .unwrap_or_else(|err| err.into_inner());
+        }
+        if state.stopped {
+            return;
+        }
+        Shared::push(&mut state, report);
+        self.shared.changed.notify_all();
+    }
+
+    /// Queue `error` if there is room in the queue. Returns `false` if the report was dropped because the queue is full or the reporter stopped (but not if it was dropped by the rate limit).
+    /// This never blocks, so it can also be used in async code.
+    pub fn try_report<E: Display>(&self, error: &TracedError<E>) -> bool {
+        self.try_submit(ErrorReport::new(error))
+    }
+
+    /// Like `try_report`, but for a report that was already created.
+    pub(crate) fn try_submit(&self, report: ErrorReport) -> bool {
+        let mut state = self.shared.lock();
+        if state.queue.len() >= self.capacity || state.stopped {
+            return false;
+        }
+        let Some(report) = self.shared.admit(&mut state, report) else {
+            return true;
+        };
+        Shared::push(&mut state, report);
+        self.shared.changed.notify_all();
+        true
+    }
+
+    /// Queue `error`, waiting asynchronously for room in the queue if it is full. The rate limit is applied when the report is queued, not when the future is created.
+    pub fn report_async<E: Display>(
+        &self,
+        error: &TracedError<E>,
+    ) -> impl Future<Output = ()> + '_ {
+        ReportFuture {
+            reporter: self,
+            report: Some(ErrorReport::new(error)),
+        }
+    }
+
+    /// Write every report queued so far to the sinks and flush them, waiting until this is done.
+    pub fn flush(&self) {
+        let mut state = self.shared.lock();
+        state.flush_requested += 1;
+        let target = state.flush_requested;
+        self.shared.changed.notify_all();
+        while state.flushed < target && !state.stopped {
+            state = self
+                .shared
+                .changed
+                .wait(state)
+                .unwrap_or_else(|err| err.into_inner());
+        }
+    }
+
+    /// Like `flush`, but waiting asynchronously.
+    pub fn flush_async(&self) -> impl Future<Output = ()> + '_ {
+        let mut state = self.shared.lock();
+        state.flush_requested += 1;
+        let target = state.flush_requested;
+        self.shared.changed.notify_all();
+        FlushFuture {
+            shared: &self.shared,
+            target,
+        }
+    }
+
+    /// Write every queued report and stop the background thread.
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        self.shared.lock().shutdown = true;
+        self.shared.changed.notify_all();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for Reporter {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+    rate_limit: Option<RateLimit>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RateLimit {
+    max: u32,
+    window: Duration,
+}
+
+/// The reports with a single fingerprint in the current rate limit window.
+struct Window {
+    start: Instant,
+    reported: u32,
+    suppressed: u64,
+    last_suppressed: Option<ErrorReport>,
+}
+
+impl Window {
+    /// The last report dropped in this window, carrying the number of dropped reports.
+    fn summary(&mut self) -> Option<ErrorReport> {
+        let mut summary = self.last_suppressed.take()?;
+        summary.suppressed = std::mem::take(&mut self.suppressed);
+        Some(summary)
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Apply the rate limit to `report`, returning it if it should be queued. Fatal reports are never dropped.
+    fn admit(&self, state: &mut State, report: ErrorReport) -> Option<ErrorReport> {
+        let Some(limit) = self
+            .rate_limit
+            .filter(|_| report.severity < Severity::Fatal)
+        else {
+            return Some(report);
+        };
+        let now = Instant::now();
+        let window = state
+            .windows
+            .entry(report.fingerprint)
+            .or_insert_with(|| Window {
+                start: now,
+                reported: 0,
+                suppressed: 0,
+                last_suppressed: None,
+            });
+        if now.duration_since(window.start) >= limit.window {
+            if let Some(summary) = window.summary() {
+                state.queue.push_back(summary);
+            }
+            window.start = now;
+            window.reported = 0;
+        }
+
+        if window.reported < limit.max {
+            window.reported += 1;
+            Some(report)
+        } else {
+            window.suppressed += 1;
+            window.last_suppressed = Some(report);
+            None
+        }
+    }
+
+    /// Queue the summaries of rate limit windows that ended, or of all windows if `all` is set, and forget windows without dropped reports.
+    fn summarize(&self, state: &mut State, all: bool) {
+        let Some(limit) = self.rate_limit else {
+            return;
+        };
+        let now = Instant::now();
+        let mut summaries = Vec::new();
+        state.windows.retain(|_, window| {
+            let ended = now.duration_since(window.start) >= limit.window;
+            if ended || all {
+                summaries.extend(window.summary());
+            }
+            !ended
+        });
+        state.queue.extend(summaries);
+    }
+
+    /// Queue `report`. Fatal reports request a flush, so they are written right away instead of waiting for a batch to fill up.
+    fn push(state: &mut State, report: ErrorReport) {
+        if report.severity == Severity::Fatal {
+            state.flush_requested += 1;
+        }
+        state.queue.push_back(report);
+    }
+
+    /// Wake every task waiting in `report_async` or `flush_async`.
+    fn wake_all(state: &mut State) {
+        for waker in state.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    queue: VecDeque<ErrorReport>,
+    flush_requested: u64,
+    flushed: u64,
+    shutdown: bool,
+    stopped: bool,
+    wakers: Vec<Waker>,
+    windows: HashMap<u64, Window>,
+}
+
+/// The background thread of a `Reporter`.
+struct Worker {
+    shared: Arc<Shared>,
+    sinks: Vec<Box<dyn Sink>>,
+    batch_size: usize,
+    flush_interval: Duration,
+}
+
+impl Worker {
+    fn run(mut self) {
+        loop {
+            let mut state = self.shared.lock();
+            if state.queue.len() < self.batch_size
+                && state.flushed == state.flush_requested
+                && !state.shutdown
+            {
+                state = self
+                    .shared
+                    .changed
+                    .wait_timeout_while(state, self.flush_interval, |state| {
+                        state.queue.len() < self.batch_size
+                            && state.flushed == state.flush_requested
+                            && !state.shutdown
+                    })
+                    .unwrap_or_else(|err| err.into_inner())
+                    .0;
+            }
+
+            let flush_target = state.flush_requested;
+            let flush = flush_target > state.flushed || state.shutdown;
+            self.shared.summarize(&mut state, flush);
+            let reports: Vec<ErrorReport> = state.queue.drain(..).collect();
+            let shutdown = state.shutdown;
+            // Room in the queue became available
+            Shared::wake_all(&mut state);
+            self.shared.changed.notify_all();
+            drop(state);
+
+            for batch in reports.chunks(self.batch_size) {
+                for sink in &mut self.sinks {
+                    call_sink(sink, |sink| sink.write_batch(batch));
+                }
+            }
+            if flush {
+                for sink in &mut self.sinks {
+                    call_sink(sink, |sink| sink.flush());
+                }
+            }
+
+            let mut state = self.shared.lock();
+            state.flushed = flush_target;
+            let stop = shutdown && state.queue.is_empty();
+            state.stopped = stop;
+            Shared::wake_all(&mut state);
+            self.shared.changed.notify_all();
+            if stop {
+                return;
+            }
+        }
+    }
+}
+
+/// Marks the reporter as stopped when the background thread exits, even if it panicked, so callers waiting for it don't block forever.
+impl Drop for Worker {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.stopped = true;
+        Shared::wake_all(&mut state);
+        self.shared.changed.notify_all();
+    }
+}
+
+/// Call `f` with `sink`, catching panics so a failing sink neither stops the background thread nor keeps the other sinks from receiving reports. The panic is still reported by the panic hook.
+fn call_sink(sink: &mut Box<dyn Sink>, f: impl FnOnce(&mut dyn Sink)) {
+    let _ = catch_unwind(AssertUnwindSafe(|| f(sink.as_mut())));
+}
+
+/// The future returned by `Reporter::report_async`.
+struct ReportFuture<'a> {
+    reporter: &'a Reporter,
+    report: Option<ErrorReport>,
+}
+
+impl Future for ReportFuture<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.report.is_none() {
+            return Poll::Ready(());
+        }
+        let mut state = self.reporter.shared.lock();
+        if state.stopped {
+            self.report = None;
+            return Poll::Ready(());
+        }
+        if state.queue.len() >= self.reporter.capacity {
+            state.wakers.push(cx.waker().clone());
+            return Poll::Pending;
+        }
+        // The rate limit only applies once the report is actually queued, so futures that are never polled don't use up its budget
+        let report = self
+            .report
+            .take()
+            .and_then(|report| self.reporter.shared.admit(&mut state, report));
+        if let Some(report) = report {
+            Shared::push(&mut state, report);
+        }
+        drop(state);
+        self.reporter.shared.changed.notify_all();
+        Poll::Ready(())
+    }
+}
+
+/// The future returned by `Reporter::flush_async`.
+struct FlushFuture<'a> {
+    shared: &'a Shared,
+    target: u64,
+}
+
+impl Future for FlushFuture<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.shared.lock();
+        if state.flushed >= self.target || state.stopped {
+            Poll::Ready(())
+        } else {
+            state.wakers.push(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use std::{future::Future, panic::Location};
+
+use crate::{
+    config::records_frame, Config, Frame, FrameKind, Trace, TraceFrame, TracedError, TracedResult,
+};
+
+/// Run `operation` until it succeeds, at most `attempts` times (but at least once).
+/// If every attempt fails, the error of the last attempt is returned. Its call stack contains the frames of all attempts, each followed by a `FrameKind::Retry` frame with the caller location of this function, and the attachments of the last attempt. Like any call stack, it is limited by `Config::max_depth`, `Config::origin_only` and `Config::frame_filter`.
+#[track_caller]
+pub fn retry<T, E>(
+    attempts: u32,
+    mut operation: impl FnMut() -> TracedResult<T, E>,
+) -> TracedResult<T, E> {
+    let mut history = History::new(Location::caller());
+    loop {
+        match operation() {
+            TracedResult::Ok(ok) => return TracedResult::Ok(ok),
+            TracedResult::Err(err) => {
+                if let Some(err) = history.record(err, attempts) {
+                    return TracedResult::Err(err);
+                }
+            }
+        }
+    }
+}
+
+/// Like `retry`, but for operations returning a `Future`. No delay is added between attempts.
+#[track_caller]
+pub fn retry_async<T, E, F, Fut>(
+    attempts: u32,
+    mut operation: F,
+) -> impl Future<Output = TracedResult<T, E>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = TracedResult<T, E>>,
+{
+    let mut history = History::new(Location::caller());
+    async move {
+        loop {
+            match operation().await {
+                TracedResult::Ok(ok) => return TracedResult::Ok(ok),
+                TracedResult::Err(err) => {
+                    if let Some(err) = history.record(err, attempts) {
+                        return TracedResult::Err(err);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The frames of all failed attempts so far.
+struct History {
+    location: &'static Location<'static>,
+    frames: Vec<Frame>,
+    attempt: u32,
+}
+
+impl History {
+    fn new(location: &'static Location<'static>) -> Self {
+        Self {
+            location,
+            frames: Vec::new(),
+            attempt: 0,
+        }
+    }
+
+    /// Add a failed attempt to the history. Returns the final error once all attempts are used up.
+    fn record<E>(&mut self, err: TracedError<E>, attempts: u32) -> Option<TracedError<E>> {
+        self.attempt += 1;
+        let TracedError {
+            trace,
+            inner,
+            attachments,
+        } = err;
+        // Like a single call stack, the history stops growing at `Config::max_depth`
+        let room = Config::current().max_depth.map_or(usize::MAX, |max_depth| {
+            max_depth.saturating_sub(self.frames.len())
+        });
+        self.frames
+            .extend(trace.into_frames().into_iter().take(room));
+        if records_frame(self.location, self.frames.len()) {
+            self.frames
+                .push(Frame::from(self.location).with_kind(FrameKind::Retry {
+                    attempt: self.attempt,
+                }));
+        }
+
+        (self.attempt >= attempts).then(|| TracedError {
+            trace: Trace::new(std::mem::take(&mut self.frames)),
+            inner,
+            attachments,
+        })
+    }
+}
@@ -0,0 +1,90 @@
+use std::{any::type_name, borrow::Cow, fmt::Display};
+
+use sentry_core::{
+    protocol::{Event, Exception, Frame, Level, Stacktrace},
+    types::Uuid,
+};
+
+use crate::{build_info::build_info, format::scrub, Severity, TracedError};
+
+impl<E: Display> TracedError<E> {
+    /// Convert this error into a Sentry event.
+    /// The event's stack trace consists of the locations in this error's call stack, and its fingerprint is derived from the error type and the location where the error was created, so Sentry groups events by where errors originate rather than by their messages.
+    /// The event's level is taken from the error's severity (see `with_severity`), and the error's tags (see `tag`) become tags of the event.
+    /// If build info was installed using `build_info::set_build_info`, it is used as the event's release and tags.
+    /// The message and tags are passed through the `Scrubber` installed with `Config::scrubber`.
+    pub fn to_sentry_event(&self) -> Event<'static> {
+        let frames = self
+            .trace()
+            .iter()
+            .rev()
+            .map(|location| Frame {
+                filename: Some(location.file().to_owned()),
+                lineno: Some(location.line().into()),
+                colno: Some(location.column().into()),
+                in_app: Some(true),
+                ..Default::default()
+            })
+            .collect();
+
+        let mut fingerprint = vec![Cow::Borrowed(type_name::<E>())];
+        if let Some(origin) = self.trace().first() {
+            fingerprint.push(Cow::Owned(origin.to_string()));
+        }
+
+        let level = match self.severity().unwrap_or_default() {
+            Severity::Warning => Level::Warning,
+            Severity::Error => Level::Error,
+            Severity::Fatal => Level::Fatal,
+        };
+        let mut event = Event {
+            level,
+            fingerprint: Cow::Owned(fingerprint),
+            exception: vec![Exception {
+                ty: type_name::<E>().to_owned(),
+                value: Some(self.scrubbed_message()),
+                stacktrace: Some(Stacktrace {
+                    frames,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }]
+            .into(),
+            ..Default::default()
+        };
+
+        if let Some(code) = self.code() {
+            event.tags.insert("error_code".to_owned(), code.to_owned());
+        }
+        for (key, value) in self.tags() {
+            event.tags.insert(key.to_owned(), scrub(value).into_owned());
+        }
+        if let Some(info) = build_info() {
+            event.release = Some(format!("{}@{}", info.crate_name, info.crate_version).into());
+            if let Some(git_hash) = info.git_hash {
+                event
+                    .tags
+                    .insert("git_hash".to_owned(), git_hash.to_owned());
+            }
+            event
+                .tags
+                .insert("profile".to_owned(), info.profile.to_owned());
+            event.tags.insert(
+                "target".to_owned(),
+                format!("{}-{}", info.target_arch, info.target_os),
+            );
+        }
+        event
+    }
+}
+
+impl<E: Display> From<TracedError<E>> for Event<'static> {
+    fn from(error: TracedError<E>) -> Self {
+        error.to_sentry_event()
+    }
+}
+
+/// Send `error` to Sentry using the current hub, returning the id of the captured event.
+pub fn capture_traced_error<E: Display>(error: &TracedError<E>) -> Uuid {
+    sentry_core::capture_event(error.to_sentry_event())
+}
@@ -0,0 +1,101 @@
+use std::{fmt::Display, panic::Location, sync::Arc};
+
+use crate::{
+    config::records_frame,
+    format::{FormatOptions, Formatted},
+    Frame, FrameKind, TraceFrame, TracedError,
+};
+
+/// A `TracedError` that can be cloned in constant time, e.g. to hand the same failure to every waiter of a shared future or subscriber of a watch channel.
+/// The error and the call stack it had when it was shared are stored behind an `Arc`. Each clone can continue the call stack on its own; frames added after sharing are copied only when a clone that shares them adds another frame.
+#[derive(Debug)]
+pub struct SharedTracedError<E> {
+    error: Arc<TracedError<E>>,
+    frames: Arc<Vec<Frame>>,
+}
+
+impl<E> SharedTracedError<E> {
+    pub fn new(error: TracedError<E>) -> Self {
+        Self {
+            error: Arc::new(error),
+            frames: Default::default(),
+        }
+    }
+
+    /// The error as it was shared, without the frames added since.
+    pub fn error(&self) -> &TracedError<E> {
+        &self.error
+    }
+
+    pub fn inner(&self) -> &E {
+        &self.error.inner
+    }
+
+    /// The full call stack: the frames of the shared error, followed by the frames added to this clone since.
+    pub fn trace(&self) -> impl DoubleEndedIterator<Item = &Frame> {
+        self.error.trace().iter().chain(self.frames.iter())
+    }
+
+    /// Add the caller location of this method to the call stack of this clone only.
+    #[track_caller]
+    pub fn propagate(&mut self) {
+        self.record_at(Location::caller(), FrameKind::Propagation);
+    }
+
+    /// Like `propagate`, but taking and returning the error by value.
+    #[track_caller]
+    pub fn propagated(mut self) -> Self {
+        self.propagate();
+        self
+    }
+
+    /// Add a frame of the specified kind at `location` to the call stack of this clone only. Like for a `TracedError`, the frame is skipped if only origins are recorded, the full call stack already reached `Config::max_depth`, or `Config::frame_filter` rejects the location.
+    pub fn record_at(&mut self, location: &'static Location<'static>, kind: FrameKind) {
+        if records_frame(location, self.error.trace().len() + self.frames.len()) {
+            Arc::make_mut(&mut self.frames).push(Frame::from(location).with_kind(kind));
+        }
+    }
+
+    /// Whether `self` and `other` are clones of the same shared error.
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.error, &other.error)
+    }
+
+    /// Convert this into a regular `TracedError` with the full call stack. The error is only cloned if other clones of it still exist.
+    pub fn into_traced_error(self) -> TracedError<E>
+    where
+        E: Clone,
+    {
+        let mut error = Arc::unwrap_or_clone(self.error);
+        error.trace.extend(self.frames.iter().copied());
+        error
+    }
+
+    /// Format this error using the specified options. See `TracedError::display_with`.
+    pub fn display_with(&self, options: FormatOptions) -> Formatted<'_, E> {
+        Formatted::with_trace(&self.error, self.trace().copied().collect(), options)
+    }
+}
+
+impl<E> Clone for SharedTracedError<E> {
+    fn clone(&self) -> Self {
+        Self {
+            error: self.error.clone(),
+            frames: self.frames.clone(),
+        }
+    }
+}
+
+impl<E> From<TracedError<E>> for SharedTracedError<E> {
+    fn from(error: TracedError<E>) -> Self {
+        Self::new(error)
+    }
+}
+
+impl<E: Display> Display for SharedTracedError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.display_with(FormatOptions::default()).fmt(f)
+    }
+}
+
+impl<E: std::error::Error> std::error::Error for SharedTracedError<E> {}
@@ -0,0 +1,84 @@
+use std::{any::type_name, fmt::Display};
+
+use crate::{Severity, TracedError};
+
+/// The SD-ID of the structured data element created by `TracedError::to_structured_data`.
+/// `32473` is the private enterprise number reserved for documentation by RFC 5612.
+pub const SD_ID: &str = "traced@32473";
+
+impl Severity {
+    /// The syslog priority (as used for the journal's `PRIORITY` field) matching this severity: 4 (warning), 3 (err) or 2 (crit).
+    pub fn syslog_priority(self) -> u8 {
+        match self {
+            Severity::Warning => 4,
+            Severity::Error => 3,
+            Severity::Fatal => 2,
+        }
+    }
+}
+
+impl<E: Display> TracedError<E> {
+    /// The fields describing this error in the systemd journal: `MESSAGE`, `PRIORITY` (see `Severity::syslog_priority`), `CODE_FILE`, `CODE_LINE` and `CODE_FUNC` for its origin (if known), and the custom fields `ERROR_TYPE`, `ERROR_CODE` (see `with_code`), `ERROR_SEVERITY`, `TRACED_FRAME_COUNT` and `TRACED_FRAMES`, which lists every frame on its own line, origin first.
+    pub fn journald_fields(&self) -> Vec<(&'static str, String)> {
+        let severity = self.severity().unwrap_or_default();
+        let mut fields = vec![
+            ("MESSAGE", self.scrubbed_message()),
+            ("PRIORITY", severity.syslog_priority().to_string()),
+        ];
+        if let Some(origin) = self.trace().first() {
+            fields.push(("CODE_FILE", origin.file().to_owned()));
+            fields.push(("CODE_LINE", origin.line().to_string()));
+            if let Some(function) = origin.function() {
+                fields.push(("CODE_FUNC", function.to_owned()));
+            }
+        }
+        fields.push(("ERROR_TYPE", type_name::<E>().to_owned()));
+        if let Some(code) = self.code() {
+            fields.push(("ERROR_CODE", code.to_owned()));
+        }
+        fields.push(("ERROR_SEVERITY", severity.as_str().to_owned()));
+        fields.push(("TRACED_FRAME_COUNT", self.trace().len().to_string()));
+        let frames: Vec<String> = self.trace().iter().map(ToString::to_string).collect();
+        fields.push(("TRACED_FRAMES", frames.join("\n")));
+        fields
+    }
+
+    /// An RFC 5424 structured data element (with the SD-ID `SD_ID`) describing this error, for the STRUCTURED-DATA part of a syslog message.
+    /// It contains the parameters `type`, `code` (if set), `file` and `line` of the origin, `severity`, `frame_count`, and a `frame` parameter for every frame, origin first.
+    pub fn to_structured_data(&self) -> String {
+        let mut element = format!("[{SD_ID}");
+        push_param(&mut element, "type", type_name::<E>());
+        if let Some(code) = self.code() {
+            push_param(&mut element, "code", code);
+        }
+        if let Some(origin) = self.trace().first() {
+            push_param(&mut element, "file", origin.file());
+            push_param(&mut element, "line", &origin.line().to_string());
+        }
+        push_param(
+            &mut element,
+            "severity",
+            self.severity().unwrap_or_default().as_str(),
+        );
+        push_param(&mut element, "frame_count", &self.trace().len().to_string());
+        for frame in self.trace() {
+            push_param(&mut element, "frame", &frame.to_string());
+        }
+        element.push(']');
+        element
+    }
+}
+
+/// Append ` name="value"` to `element`, escaping `"`, `\` and `]` in the value as required by RFC 5424.
+fn push_param(element: &mut String, name: &str, value: &str) {
+    element.push(' ');
+    element.push_str(name);
+    element.push_str("=\"");
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            element.push('\\');
+        }
+        element.push(c);
+    }
+    element.push('"');
+}
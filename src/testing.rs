@@ -0,0 +1,130 @@
+use crate::{Frame, FrameKind, Trace, TracedError};
+
+/// Builds `TracedError`s with deterministic call stacks, so tests of code consuming traced errors (formatters, filters, reporters, ...) don't depend on real source positions.
+/// For example, `TraceBuilder::new().frame("src/a.rs", 10, 5).frame("src/b.rs", 20, 9).build(MyError)` creates an error originating in `src/a.rs` that was propagated once in `src/b.rs`.
+#[derive(Debug, Clone, Default)]
+pub struct TraceBuilder {
+    frames: Vec<Frame>,
+}
+
+impl TraceBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a frame to the call stack. The first frame added is the error's origin, every later one is a propagation.
+    pub fn frame(self, file: &'static str, line: u32, column: u32) -> Self {
+        let kind = if self.frames.is_empty() {
+            FrameKind::Origin
+        } else {
+            FrameKind::Propagation
+        };
+        self.frame_of_kind(file, line, column, kind)
+    }
+
+    /// Add a frame of the specified kind to the call stack.
+    pub fn frame_of_kind(
+        mut self,
+        file: &'static str,
+        line: u32,
+        column: u32,
+        kind: FrameKind,
+    ) -> Self {
+        self.frames.push(Frame::new(file, line, column, kind));
+        self
+    }
+
+    /// Create a `TracedError` with the specified error and the call stack built so far.
+    pub fn build<E>(self, inner: E) -> TracedError<E> {
+        TracedError {
+            trace: Trace::new(self.frames),
+            inner,
+            attachments: Default::default(),
+        }
+    }
+}
+
+/// A single entry of a `TraceDiff`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameDiff {
+    /// The frame is present in both call stacks.
+    Same(Frame),
+    /// The frame is only present in the actual call stack.
+    Added(Frame),
+    /// The frame is only present in the expected call stack.
+    Removed(Frame),
+    /// The frame is present in both call stacks, but at a different position in the same file.
+    Changed { expected: Frame, actual: Frame },
+}
+
+/// The differences between two call stacks, e.g. to assert that a refactoring didn't change the path an error takes.
+/// Its `Display` implementation lists the frames of both call stacks in the order they were recorded, prefixing added frames with `+`, removed frames with `-` and changed frames with `~`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceDiff {
+    entries: Vec<FrameDiff>,
+}
+
+impl TraceDiff {
+    /// Compare an actual call stack (e.g. `error.trace()`) against an expected one.
+    pub fn new(expected: &[Frame], actual: &[Frame]) -> Self {
+        // lcs[i][j] is the length of the longest common subsequence of expected[i..] and actual[j..]
+        let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+        for i in (0..expected.len()).rev() {
+            for j in (0..actual.len()).rev() {
+                lcs[i][j] = if expected[i] == actual[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let mut entries = Vec::with_capacity(expected.len().max(actual.len()));
+        let (mut i, mut j) = (0, 0);
+        while i < expected.len() || j < actual.len() {
+            if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+                entries.push(FrameDiff::Same(expected[i]));
+                i += 1;
+                j += 1;
+            } else if j == actual.len() || (i < expected.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+                entries.push(FrameDiff::Removed(expected[i]));
+                i += 1;
+            } else {
+                let actual = actual[j];
+                match entries.last() {
+                    Some(&FrameDiff::Removed(expected)) if expected.file() == actual.file() => {
+                        *entries.last_mut().unwrap() = FrameDiff::Changed { expected, actual };
+                    }
+                    _ => entries.push(FrameDiff::Added(actual)),
+                }
+                j += 1;
+            }
+        }
+        Self { entries }
+    }
+
+    pub fn entries(&self) -> &[FrameDiff] {
+        &self.entries
+    }
+
+    /// Returns `true` if the call stacks differ.
+    pub fn has_changes(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| !matches!(entry, FrameDiff::Same(_)))
+    }
+}
+
+impl std::fmt::Display for TraceDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            match entry {
+                FrameDiff::Same(frame) => writeln!(f, "  {frame}")?,
+                FrameDiff::Added(frame) => writeln!(f, "+ {frame}")?,
+                FrameDiff::Removed(frame) => writeln!(f, "- {frame}")?,
+                FrameDiff::Changed { expected, actual } => writeln!(f, "~ {expected} -> {actual}")?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    convert::Infallible,
+    ops::{ControlFlow, FromResidual, Residual, Try},
+};
+
+use crate::{FrameKind, TracedError, TracedResult};
+
+/// A `TracedResult` whose error, along with its call stack, is stored behind a single `Box`.
+/// This keeps the result at most one pointer larger than `T`, which avoids copying a large `Err` variant with every return on the success path, at the cost of an allocation per error.
+/// `?` works between `ThinTracedResult`s, `TracedResult`s and `Result`s in the same way it works for `TracedResult`.
+#[derive(Debug, Clone)]
+#[must_use = "this `ThinTracedResult` may be an `Err` variant, which should be handled"]
+pub enum ThinTracedResult<T, E> {
+    Ok(T),
+    Err(Box<TracedError<E>>),
+}
+
+impl<T, E> ThinTracedResult<T, E> {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, ThinTracedResult::Ok(_))
+    }
+
+    pub fn is_err(&self) -> bool {
+        matches!(self, ThinTracedResult::Err(_))
+    }
+
+    /// Move the error out of its box and convert into a regular `TracedResult`.
+    pub fn into_traced_result(self) -> TracedResult<T, E> {
+        match self {
+            ThinTracedResult::Ok(ok) => TracedResult::Ok(ok),
+            ThinTracedResult::Err(err) => TracedResult::Err(*err),
+        }
+    }
+}
+
+impl<T, E> From<TracedResult<T, E>> for ThinTracedResult<T, E> {
+    fn from(value: TracedResult<T, E>) -> Self {
+        match value {
+            TracedResult::Ok(ok) => ThinTracedResult::Ok(ok),
+            TracedResult::Err(err) => ThinTracedResult::Err(Box::new(err)),
+        }
+    }
+}
+
+impl<T, E> From<ThinTracedResult<T, E>> for TracedResult<T, E> {
+    fn from(value: ThinTracedResult<T, E>) -> Self {
+        value.into_traced_result()
+    }
+}
+
+impl<T, E> Try for ThinTracedResult<T, E> {
+    type Output = T;
+
+    type Residual = ThinTracedResult<Infallible, E>;
+
+    fn from_output(output: Self::Output) -> Self {
+        ThinTracedResult::Ok(output)
+    }
+
+    #[track_caller]
+    fn branch(self) -> ControlFlow<Self::Residual, Self::Output> {
+        match self {
+            ThinTracedResult::Ok(output) => ControlFlow::Continue(output),
+            ThinTracedResult::Err(mut error) => {
+                error.trace.record(FrameKind::Propagation);
+                ControlFlow::Break(ThinTracedResult::Err(error))
+            }
+        }
+    }
+}
+
+impl<T, E> Residual<T> for ThinTracedResult<Infallible, E> {
+    type TryType = ThinTracedResult<T, E>;
+}
+
+/// If the error type changes, the location of the `?` operator is added to the call stack a second time as a `FrameKind::Conversion` frame.
+impl<T, R, E: From<R>> FromResidual<ThinTracedResult<Infallible, R>> for ThinTracedResult<T, E> {
+    #[track_caller]
+    fn from_residual(residual: ThinTracedResult<Infallible, R>) -> Self {
+        match residual {
+            ThinTracedResult::Err(err) => ThinTracedResult::Err(Box::new(err.convert())),
+            ThinTracedResult::Ok(never) => match never {},
+        }
+    }
+}
+
+/// If the error type changes, the location of the `?` operator is added to the call stack a second time as a `FrameKind::Conversion` frame.
+impl<T, R, E: From<R>> FromResidual<TracedResult<Infallible, R>> for ThinTracedResult<T, E> {
+    #[track_caller]
+    fn from_residual(residual: TracedResult<Infallible, R>) -> Self {
+        match residual {
+            TracedResult::Err(err) => ThinTracedResult::Err(Box::new(err.convert())),
+            TracedResult::Ok(never) => match never {},
+        }
+    }
+}
+
+/// If the error type changes, the location of the `?` operator is added to the call stack a second time as a `FrameKind::Conversion` frame.
+impl<T, R, E: From<R>> FromResidual<ThinTracedResult<Infallible, R>> for TracedResult<T, E> {
+    #[track_caller]
+    fn from_residual(residual: ThinTracedResult<Infallible, R>) -> Self {
+        match residual {
+            ThinTracedResult::Err(err) => TracedResult::Err(err.convert()),
+            ThinTracedResult::Ok(never) => match never {},
+        }
+    }
+}
+
+/// The error is converted using `From` and wrapped in a new `TracedError` whose call stack starts at the location of the `?` operator.
+impl<T, R, E: From<R>> FromResidual<Result<Infallible, R>> for ThinTracedResult<T, E> {
+    #[track_caller]
+    fn from_residual(residual: Result<Infallible, R>) -> Self {
+        match residual {
+            Err(err) => ThinTracedResult::Err(Box::new(TracedError::new(From::from(err)))),
+            Ok(never) => match never {},
+        }
+    }
+}
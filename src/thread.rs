@@ -0,0 +1,195 @@
+use std::{
+    any::Any,
+    cell::RefCell,
+    fmt::{Debug, Display},
+    panic::Location,
+    sync::{Mutex, PoisonError},
+    thread::{JoinHandle, Scope, ScopedJoinHandle},
+};
+
+use crate::{
+    capture_enabled, panic::payload_message, Frame, FrameKind, HttpStatus, MultiTracedError, Trace,
+    TraceFrame, TracedError, TracedResult,
+};
+
+/// The error returned by `join_traced` if the thread panicked.
+/// The panic message is extracted when the error is created; the payload is kept behind a `Mutex` so the error is `Sync` and can be propagated into e.g. `Traced<()>`.
+pub struct ThreadPanic {
+    thread: Option<String>,
+    message: Option<String>,
+    payload: Mutex<Box<dyn Any + Send>>,
+}
+
+impl ThreadPanic {
+    /// The name of the thread that panicked, if it had one.
+    pub fn thread(&self) -> Option<&str> {
+        self.thread.as_deref()
+    }
+
+    /// The panic message, if the payload is a string as created by `panic!`.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// The payload of the panic, e.g. to continue unwinding using `std::panic::resume_unwind`.
+    pub fn into_payload(self) -> Box<dyn Any + Send> {
+        self.payload
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn new(thread: Option<String>, payload: Box<dyn Any + Send>) -> Self {
+        Self {
+            thread,
+            message: payload_message(payload.as_ref()).map(ToOwned::to_owned),
+            payload: Mutex::new(payload),
+        }
+    }
+}
+
+impl Debug for ThreadPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ThreadPanic")
+            .field("thread", &self.thread)
+            .field("message", &self.message())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Display for ThreadPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.thread {
+            Some(thread) => write!(f, "thread '{thread}' panicked")?,
+            None => write!(f, "thread panicked")?,
+        }
+        match self.message() {
+            Some(message) => write!(f, ": {message}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ThreadPanic {}
+
+impl HttpStatus for ThreadPanic {}
+
+/// A `JoinHandle` that remembers where its thread was spawned. See `spawn_traced`.
+#[derive(Debug)]
+pub struct TracedJoinHandle<T> {
+    handle: JoinHandle<T>,
+    spawned_at: &'static Location<'static>,
+}
+
+impl<T> TracedJoinHandle<T> {
+    /// Wait for the thread to finish. If it panicked, the panic becomes a `TracedError` whose origin is where the thread was spawned, followed by the caller location of this method.
+    #[track_caller]
+    pub fn join(self) -> TracedResult<T, ThreadPanic> {
+        let thread = self.handle.thread().name().map(ToOwned::to_owned);
+        match self.handle.join() {
+            Ok(value) => TracedResult::Ok(value),
+            Err(payload) => {
+                let frames = if capture_enabled() {
+                    vec![Frame::from(self.spawned_at).with_kind(FrameKind::Origin)]
+                } else {
+                    Vec::new()
+                };
+                let mut trace = Trace::new(frames);
+                trace.record(FrameKind::Propagation);
+                TracedResult::Err(TracedError {
+                    trace,
+                    inner: ThreadPanic::new(thread, payload),
+                    attachments: Default::default(),
+                })
+            }
+        }
+    }
+
+    /// The underlying `JoinHandle`.
+    pub fn into_inner(self) -> JoinHandle<T> {
+        self.handle
+    }
+}
+
+/// Like `std::thread::spawn`, but the returned handle remembers the caller location of this function, so `join_traced` can report a panic of the thread as a traced error originating there.
+#[track_caller]
+pub fn spawn_traced<F, T>(f: F) -> TracedJoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    TracedJoinHandle {
+        handle: std::thread::spawn(f),
+        spawned_at: Location::caller(),
+    }
+}
+
+/// Wait for a thread spawned using `spawn_traced` to finish, turning a panic into a traced error. See `TracedJoinHandle::join`.
+#[track_caller]
+pub fn join_traced<T>(handle: TracedJoinHandle<T>) -> TracedResult<T, ThreadPanic> {
+    handle.join()
+}
+
+/// A scope for spawning threads whose errors are collected by `scope_traced`.
+pub struct TracedScope<'scope, 'env: 'scope, E> {
+    scope: &'scope Scope<'scope, 'env>,
+    threads: RefCell<Vec<ScopedThread<'scope, E>>>,
+}
+
+/// A thread spawned using `TracedScope::spawn`.
+struct ScopedThread<'scope, E> {
+    handle: ScopedJoinHandle<'scope, TracedResult<(), E>>,
+    spawned_at: &'static Location<'static>,
+}
+
+impl<'scope, 'env, E: Send + 'scope> TracedScope<'scope, 'env, E> {
+    /// Spawn a scoped thread. If it fails, its error is collected by `scope_traced`, with the caller location of this method added to the error's call stack.
+    #[track_caller]
+    pub fn spawn<F>(&self, f: F)
+    where
+        F: FnOnce() -> TracedResult<(), E> + Send + 'scope,
+    {
+        self.threads.borrow_mut().push(ScopedThread {
+            handle: self.scope.spawn(f),
+            spawned_at: Location::caller(),
+        });
+    }
+}
+
+/// Like `std::thread::scope`, but collects the errors of all threads spawned using `TracedScope::spawn` into a `MultiTracedError`, whose own call stack starts at the caller location of this function.
+/// Each collected error's call stack ends with a frame at the location its thread was spawned. If a thread panics, the panic is resumed once all threads finished, like `std::thread::scope` does.
+#[track_caller]
+pub fn scope_traced<'env, E, T>(
+    f: impl for<'scope> FnOnce(&TracedScope<'scope, 'env, E>) -> T,
+) -> TracedResult<T, MultiTracedError<E>>
+where
+    E: Send + 'env,
+{
+    let (value, errors) = std::thread::scope(|scope| {
+        let traced = TracedScope {
+            scope,
+            threads: RefCell::new(Vec::new()),
+        };
+        let value = f(&traced);
+        let errors: MultiTracedError<E> = traced
+            .threads
+            .into_inner()
+            .into_iter()
+            .filter_map(|thread| match thread.handle.join() {
+                Ok(TracedResult::Ok(())) => None,
+                Ok(TracedResult::Err(mut err)) => {
+                    err.trace
+                        .record_at(thread.spawned_at, FrameKind::Propagation);
+                    Some(err)
+                }
+                Err(payload) => std::panic::resume_unwind(payload),
+            })
+            .collect();
+        (value, errors)
+    });
+
+    if errors.is_empty() {
+        TracedResult::Ok(value)
+    } else {
+        TracedResult::Err(TracedError::new(errors))
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt::Display;
+
+use ::tonic::{metadata::MetadataValue, Code, Status};
+
+use crate::{HttpStatus, TracedError};
+
+/// The binary metadata key under which the call stack of a `TracedError` is sent along with its `Status`.
+pub const FRAMES_METADATA_KEY: &str = "traced-frames-bin";
+
+/// Converts the error into a `Status` whose code corresponds to the error's `HttpStatus` (e.g. `NotFound` for `404`, `Internal` for `500`), carrying the call stack in the `FRAMES_METADATA_KEY` metadata entry.
+/// Use `continue_trace` on the client side to get the call stack back.
+impl<E: Display + HttpStatus> From<TracedError<E>> for Status {
+    fn from(error: TracedError<E>) -> Self {
+        let frames = error
+            .trace()
+            .iter()
+            .map(|frame| frame.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut status = Status::new(grpc_code(error.inner.status()), error.scrubbed_message());
+        status.metadata_mut().insert_bin(
+            FRAMES_METADATA_KEY,
+            MetadataValue::from_bytes(frames.as_bytes()),
+        );
+        status
+    }
+}
+
+/// The gRPC status code corresponding to an HTTP status code. Since the status is always built from an error, success statuses map to `Code::Unknown` rather than `Code::Ok`.
+fn grpc_code(status: u16) -> Code {
+    match status {
+        200..=299 => Code::Unknown,
+        400 => Code::InvalidArgument,
+        401 => Code::Unauthenticated,
+        403 => Code::PermissionDenied,
+        404 => Code::NotFound,
+        409 => Code::AlreadyExists,
+        412 => Code::FailedPrecondition,
+        429 => Code::ResourceExhausted,
+        499 => Code::Cancelled,
+        501 => Code::Unimplemented,
+        503 => Code::Unavailable,
+        504 => Code::DeadlineExceeded,
+        status if (400..500).contains(&status) => Code::FailedPrecondition,
+        _ => Code::Internal,
+    }
+}
+
+/// A source location recorded by another service. `column` is `0` if the service was built with the `no-columns` feature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteFrame {
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl RemoteFrame {
+    /// Parses `file:line:column`, or `file:line` if the service was built with the `no-columns` feature.
+    fn parse(frame: &str) -> Option<Self> {
+        let (rest, last) = frame.rsplit_once(':')?;
+        let last = last.parse().ok()?;
+        let (file, line, column) = match rest.rsplit_once(':') {
+            Some((file, line)) if line.parse::<u32>().is_ok() => (file, line.parse().ok()?, last),
+            _ => (rest, last, 0),
+        };
+        Some(Self {
+            file: file.to_owned(),
+            line,
+            column,
+        })
+    }
+}
+
+/// Formats the frame as `file:line:column`, omitting the column if the `no-columns` feature is enabled, like `Frame`.
+impl Display for RemoteFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)?;
+        if !cfg!(feature = "no-columns") {
+            write!(f, ":{}", self.column)?;
+        }
+        Ok(())
+    }
+}
+
+/// A `Status` received from a service, along with the call stack the service recorded for it (if any).
+#[derive(Debug, Clone)]
+pub struct RemoteStatus {
+    status: Status,
+    remote_trace: Vec<RemoteFrame>,
+}
+
+impl RemoteStatus {
+    pub fn status(&self) -> &Status {
+        &self.status
+    }
+
+    /// The frames recorded by the remote service, in the order they were recorded.
+    pub fn remote_trace(&self) -> &[RemoteFrame] {
+        &self.remote_trace
+    }
+
+    pub fn into_status(self) -> Status {
+        self.status
+    }
+}
+
+impl From<Status> for RemoteStatus {
+    fn from(status: Status) -> Self {
+        let remote_trace = status
+            .metadata()
+            .get_bin(FRAMES_METADATA_KEY)
+            .and_then(|value| value.to_bytes().ok())
+            .map(|bytes| {
+                String::from_utf8_lossy(&bytes)
+                    .lines()
+                    .filter_map(RemoteFrame::parse)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            status,
+            remote_trace,
+        }
+    }
+}
+
+impl Display for RemoteStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}: {}", self.status.code(), self.status.message())?;
+        for frame in self.remote_trace.iter().rev() {
+            let position = if cfg!(feature = "no-columns") {
+                frame.line.to_string()
+            } else {
+                format!("{}:{}", frame.line, frame.column)
+            };
+            writeln!(f, "At ({position}) in {file} (remote)", file = frame.file)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for RemoteStatus {}
+
+/// Maps the gRPC status code back to an HTTP status code, so errors received from other services can be returned by HTTP handlers.
+impl HttpStatus for RemoteStatus {
+    fn status(&self) -> u16 {
+        match self.status.code() {
+            Code::Ok => 200,
+            Code::InvalidArgument | Code::OutOfRange => 400,
+            Code::Unauthenticated => 401,
+            Code::PermissionDenied => 403,
+            Code::NotFound => 404,
+            Code::AlreadyExists | Code::Aborted => 409,
+            Code::FailedPrecondition => 412,
+            Code::ResourceExhausted => 429,
+            Code::Cancelled => 499,
+            Code::Unimplemented => 501,
+            Code::Unavailable => 503,
+            Code::DeadlineExceeded => 504,
+            _ => 500,
+        }
+    }
+}
+
+/// Continue the call stack of an error received from another service.
+/// The caller location of this function becomes the first local entry in the returned error's call stack, while the frames recorded by the remote service are available through `RemoteStatus::remote_trace`.
+#[track_caller]
+pub fn continue_trace(status: Status) -> TracedError<RemoteStatus> {
+    TracedError::new(status.into())
+}
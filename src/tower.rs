@@ -0,0 +1,126 @@
+use std::{
+    fmt::Display,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use ::tower::{Layer, Service};
+
+use crate::{reporter::ErrorReport, TracedError};
+
+/// Provides additional information about a request which is included in the events emitted by `TracedErrorLog`.
+pub trait RequestMetadata<Req> {
+    fn metadata(&self, request: &Req) -> Option<String>;
+}
+
+/// The default `RequestMetadata`, which does not record anything about the request.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoMetadata;
+
+impl<Req> RequestMetadata<Req> for NoMetadata {
+    fn metadata(&self, _: &Req) -> Option<String> {
+        None
+    }
+}
+
+impl<Req, F: Fn(&Req) -> String> RequestMetadata<Req> for F {
+    fn metadata(&self, request: &Req) -> Option<String> {
+        Some(self(request))
+    }
+}
+
+/// A `tower::Layer` which wraps services returning `TracedError`s in a `TracedErrorLog`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TracedErrorLogLayer<M = NoMetadata> {
+    metadata: M,
+}
+
+impl TracedErrorLogLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<M> TracedErrorLogLayer<M> {
+    /// Use `metadata` to describe the request that caused an error, e.g. `|req: &Request<_>| req.uri().to_string()`.
+    pub fn with_metadata<N>(self, metadata: N) -> TracedErrorLogLayer<N> {
+        TracedErrorLogLayer { metadata }
+    }
+}
+
+impl<S, M: Clone> Layer<S> for TracedErrorLogLayer<M> {
+    type Service = TracedErrorLog<S, M>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TracedErrorLog {
+            inner,
+            metadata: self.metadata.clone(),
+        }
+    }
+}
+
+/// A middleware that emits a `tracing` event containing the error and its call stack whenever the inner service fails with a `TracedError`.
+/// The event includes an `ErrorReport` of the error in its `traced_error` field, which `tracing_subscriber::TracedErrorLayer` passes on to a `Reporter`.
+/// The error itself is passed on unchanged.
+#[derive(Clone, Copy, Debug)]
+pub struct TracedErrorLog<S, M = NoMetadata> {
+    inner: S,
+    metadata: M,
+}
+
+impl<S, M, Req, E> Service<Req> for TracedErrorLog<S, M>
+where
+    S: Service<Req, Error = TracedError<E>>,
+    M: RequestMetadata<Req>,
+    E: Display,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TracedErrorLogFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Req) -> Self::Future {
+        let metadata = self.metadata.metadata(&request);
+        TracedErrorLogFuture {
+            inner: self.inner.call(request),
+            metadata,
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The response future of `TracedErrorLog`.
+    pub struct TracedErrorLogFuture<F> {
+        #[pin]
+        inner: F,
+        metadata: Option<String>,
+    }
+}
+
+impl<F, T, E> Future for TracedErrorLogFuture<F>
+where
+    F: Future<Output = Result<T, TracedError<E>>>,
+    E: Display,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = std::task::ready!(this.inner.poll(cx));
+        if let Err(error) = &result {
+            let frames: Vec<String> = error.trace().iter().map(ToString::to_string).collect();
+            tracing::error!(
+                error = %error.scrubbed_message(),
+                frames = ?frames,
+                traced_error = &ErrorReport::new(error) as &(dyn std::error::Error + 'static),
+                request = this.metadata.as_deref(),
+                "service failed with a traced error"
+            );
+        }
+        Poll::Ready(result)
+    }
+}
@@ -0,0 +1,72 @@
+use std::{fmt::Debug, sync::Arc};
+
+use ::tracing::{
+    field::{Field, Visit},
+    Event, Subscriber,
+};
+use ::tracing_subscriber::{layer::Context, Layer};
+
+use crate::{
+    process::parse_report_frames,
+    reporter::{ErrorReport, Reporter},
+};
+
+/// The name of the event field `TracedErrorLayer` looks for. Its value should be an `ErrorReport` recorded as an error, e.g. `tracing::error!(traced_error = &ErrorReport::new(&error) as &dyn Error, "request failed")`, which keeps the error's code, severity and tags.
+/// A report created by `TracedError::to_process_report` and recorded using `%` is accepted as well, but only carries the message and call stack. The events emitted by `tower::TracedErrorLog` include this field.
+pub const EVENT_FIELD: &str = "traced_error";
+
+/// A `tracing_subscriber::Layer` that picks up traced errors recorded as the `EVENT_FIELD` field of events, and queues them on a `Reporter` so they reach its sinks with their full call stack.
+/// The target of the event is added to each report as the `target` tag. Like `Reporter::try_report`, the layer never blocks the thread emitting the event: reports are dropped if the reporter's queue is full. Events without a valid report in that field are ignored.
+#[derive(Clone)]
+pub struct TracedErrorLayer {
+    reporter: Arc<Reporter>,
+}
+
+impl TracedErrorLayer {
+    /// Create a layer queuing reports on `reporter`. Keep a clone of it to call `Reporter::flush` before the application exits.
+    pub fn new(reporter: Arc<Reporter>) -> Self {
+        Self { reporter }
+    }
+}
+
+impl<Sub: Subscriber> Layer<Sub> for TracedErrorLayer {
+    fn on_event(&self, event: &Event<'_>, _: Context<'_, Sub>) {
+        let mut visitor = ReportVisitor(None);
+        event.record(&mut visitor);
+        if let Some(report) = visitor.0 {
+            self.reporter
+                .try_submit(report.tagged("target", event.metadata().target()));
+        }
+    }
+}
+
+struct ReportVisitor(Option<ErrorReport>);
+
+impl ReportVisitor {
+    fn parse(&mut self, report: &str) {
+        if let Some(error) = parse_report_frames(report) {
+            self.0 = Some(ErrorReport::new(&error).with_remote_frames(error.remote_trace()));
+        }
+    }
+}
+
+impl Visit for ReportVisitor {
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        if field.name() == EVENT_FIELD {
+            self.0 = value.downcast_ref::<ErrorReport>().cloned();
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == EVENT_FIELD {
+            self.parse(value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        // Fields recorded with `%` are passed as `Debug` values which format using `Display`
+        if field.name() == EVENT_FIELD {
+            self.parse(&format!("{value:?}"));
+        }
+    }
+}
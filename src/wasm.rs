@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+use js_sys::Reflect;
+use wasm_bindgen::JsValue;
+
+use crate::TracedError;
+
+impl<E: Display> TracedError<E> {
+    /// Convert this error into a JavaScript `Error` whose `stack` property is synthesized from this error's call stack, so browser devtools show where the error was created and propagated on the Rust side. The message is passed through the `Scrubber` installed with `Config::scrubber`.
+    pub fn to_js_error(&self) -> js_sys::Error {
+        let message = self.scrubbed_message();
+        let mut stack = format!("Error: {message}");
+        for location in self.trace().iter() {
+            stack.push_str(&format!("\n    at {location}"));
+        }
+
+        let error = js_sys::Error::new(&message);
+        // Setting a property on a freshly created `Error` object cannot fail
+        let _ = Reflect::set(&error, &JsValue::from_str("stack"), &JsValue::from(stack));
+        error
+    }
+}
+
+impl<E: Display> From<TracedError<E>> for js_sys::Error {
+    fn from(error: TracedError<E>) -> Self {
+        error.to_js_error()
+    }
+}
+
+impl<E: Display> From<TracedError<E>> for JsValue {
+    fn from(error: TracedError<E>) -> Self {
+        error.to_js_error().into()
+    }
+}
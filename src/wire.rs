@@ -0,0 +1,341 @@
+use std::{fmt::Display, sync::Arc};
+
+use crate::{Frame, FrameKind, TracedError};
+
+/// The first byte of every encoded call stack, to detect data in other formats or future versions of this one.
+const VERSION: u8 = 1;
+
+/// Set in the header if line numbers are encoded relative to the previous frame.
+const DELTA_LINES: u8 = 1;
+
+/// Options for `encode_with`. By default, line numbers are delta encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WireOptions {
+    delta: bool,
+}
+
+impl Default for WireOptions {
+    fn default() -> Self {
+        Self { delta: true }
+    }
+}
+
+impl WireOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Encode the line of each frame as the difference to the line of the previous frame, which is usually small since errors tend to be propagated through nearby code.
+    pub fn delta(mut self, delta: bool) -> Self {
+        self.delta = delta;
+        self
+    }
+}
+
+/// Encode `frames` into a compact binary format that can be turned back into frames using `decode`, e.g. to ship large numbers of call stacks to a collector.
+/// Numbers are encoded as variable-length integers, and every distinct string (file paths, type names, ...) is only included once and referred to by its index afterwards.
+pub fn encode(frames: &[Frame]) -> Vec<u8> {
+    encode_with(frames, WireOptions::default())
+}
+
+/// Like `encode`, but using custom options.
+pub fn encode_with(frames: &[Frame], options: WireOptions) -> Vec<u8> {
+    let mut strings = Vec::new();
+    let mut body = Vec::new();
+    write_varint(&mut body, frames.len() as u64);
+
+    let mut previous_line = 0;
+    for frame in frames {
+        let file = string_index(&mut strings, frame.file());
+        write_varint(&mut body, file);
+        if options.delta {
+            let delta = i64::from(frame.line()) - i64::from(previous_line);
+            write_varint(&mut body, ((delta << 1) ^ (delta >> 63)) as u64);
+            previous_line = frame.line();
+        } else {
+            write_varint(&mut body, frame.line().into());
+        }
+        write_varint(&mut body, frame.column().into());
+
+        // The lower 3 bits contain the kind, the upper bits mark which of the optional strings follow
+        let metadata = [
+            frame.function(),
+            frame.module_path(),
+            frame.crate_name(),
+            frame.crate_version(),
+        ];
+        let mut tag = match frame.kind() {
+            FrameKind::Origin => 0,
+            FrameKind::Propagation => 1,
+            FrameKind::Conversion { .. } => 2,
+            FrameKind::Merge => 3,
+            FrameKind::Retry { .. } => 4,
+            FrameKind::ProcessBoundary => 5,
+        };
+        for (bit, value) in metadata.iter().enumerate() {
+            if value.is_some() {
+                tag |= 1 << (bit + 3);
+            }
+        }
+        body.push(tag);
+
+        match frame.kind() {
+            FrameKind::Conversion { from, to } => {
+                let from = string_index(&mut strings, from);
+                write_varint(&mut body, from);
+                let to = string_index(&mut strings, to);
+                write_varint(&mut body, to);
+            }
+            FrameKind::Retry { attempt } => write_varint(&mut body, attempt.into()),
+            _ => {}
+        }
+        for value in metadata.into_iter().flatten() {
+            let index = string_index(&mut strings, value);
+            write_varint(&mut body, index);
+        }
+    }
+
+    let mut encoded = vec![VERSION, if options.delta { DELTA_LINES } else { 0 }];
+    write_varint(&mut encoded, strings.len() as u64);
+    for string in strings {
+        write_varint(&mut encoded, string.len() as u64);
+        encoded.extend_from_slice(string.as_bytes());
+    }
+    encoded.extend(body);
+    encoded
+}
+
+/// A frame decoded by `decode` or received from another process (see `process::ChildError`). Unlike `Frame`, it owns its strings, so decoding untrusted input doesn't keep any memory alive once the frames are dropped.
+/// Strings shared by several frames of the same call stack are only allocated once.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WireFrame {
+    file: Arc<str>,
+    line: u32,
+    column: u32,
+    kind: WireFrameKind,
+    function: Option<Arc<str>>,
+    module_path: Option<Arc<str>>,
+    crate_name: Option<Arc<str>>,
+    crate_version: Option<Arc<str>>,
+}
+
+/// The reason a `WireFrame` was recorded. See `FrameKind`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WireFrameKind {
+    Origin,
+    Propagation,
+    Conversion { from: Arc<str>, to: Arc<str> },
+    Merge,
+    Retry { attempt: u32 },
+    ProcessBoundary,
+}
+
+impl WireFrame {
+    /// A frame without function or crate metadata.
+    pub(crate) fn new(file: Arc<str>, line: u32, column: u32, kind: WireFrameKind) -> Self {
+        Self {
+            file,
+            line,
+            column,
+            kind,
+            function: None,
+            module_path: None,
+            crate_name: None,
+            crate_version: None,
+        }
+    }
+
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+
+    pub fn kind(&self) -> &WireFrameKind {
+        &self.kind
+    }
+
+    pub fn function(&self) -> Option<&str> {
+        self.function.as_deref()
+    }
+
+    pub fn module_path(&self) -> Option<&str> {
+        self.module_path.as_deref()
+    }
+
+    pub fn crate_name(&self) -> Option<&str> {
+        self.crate_name.as_deref()
+    }
+
+    pub fn crate_version(&self) -> Option<&str> {
+        self.crate_version.as_deref()
+    }
+}
+
+/// Copies a frame recorded in this process, e.g. to keep it in an owned report.
+impl From<&Frame> for WireFrame {
+    fn from(frame: &Frame) -> Self {
+        let kind = match frame.kind() {
+            FrameKind::Origin => WireFrameKind::Origin,
+            FrameKind::Propagation => WireFrameKind::Propagation,
+            FrameKind::Conversion { from, to } => WireFrameKind::Conversion {
+                from: Arc::from(from),
+                to: Arc::from(to),
+            },
+            FrameKind::Merge => WireFrameKind::Merge,
+            FrameKind::Retry { attempt } => WireFrameKind::Retry { attempt },
+            FrameKind::ProcessBoundary => WireFrameKind::ProcessBoundary,
+        };
+        Self {
+            file: Arc::from(frame.file()),
+            line: frame.line(),
+            column: frame.column(),
+            kind,
+            function: frame.function().map(Arc::from),
+            module_path: frame.module_path().map(Arc::from),
+            crate_name: frame.crate_name().map(Arc::from),
+            crate_version: frame.crate_version().map(Arc::from),
+        }
+    }
+}
+
+/// Formats the frame's location as `file:line:column`, like `Frame`.
+impl Display for WireFrame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// Decode frames encoded using `encode` or `encode_with`. Returns `None` if `bytes` is not a valid encoding; this never panics, so it can be used on untrusted input.
+pub fn decode(bytes: &[u8]) -> Option<Vec<WireFrame>> {
+    let mut reader = Reader(bytes);
+    if reader.byte()? != VERSION {
+        return None;
+    }
+    let delta = reader.byte()? & DELTA_LINES != 0;
+
+    let strings = (0..reader.varint()?)
+        .map(|_| {
+            let len = usize::try_from(reader.varint()?).ok()?;
+            let string = std::str::from_utf8(reader.take(len)?).ok()?;
+            Some(Arc::from(string))
+        })
+        .collect::<Option<Vec<Arc<str>>>>()?;
+    let string = |index: u64| strings.get(usize::try_from(index).ok()?).cloned();
+
+    let mut previous_line = 0;
+    let frames = (0..reader.varint()?)
+        .map(|_| {
+            let file = string(reader.varint()?)?;
+            let line = if delta {
+                let zigzag = reader.varint()?;
+                let delta = (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64);
+                previous_line = u32::try_from(i64::from(previous_line).checked_add(delta)?).ok()?;
+                previous_line
+            } else {
+                u32::try_from(reader.varint()?).ok()?
+            };
+            let column = u32::try_from(reader.varint()?).ok()?;
+
+            let tag = reader.byte()?;
+            let kind = match tag & 0b111 {
+                0 => WireFrameKind::Origin,
+                1 => WireFrameKind::Propagation,
+                2 => WireFrameKind::Conversion {
+                    from: string(reader.varint()?)?,
+                    to: string(reader.varint()?)?,
+                },
+                3 => WireFrameKind::Merge,
+                4 => WireFrameKind::Retry {
+                    attempt: u32::try_from(reader.varint()?).ok()?,
+                },
+                5 => WireFrameKind::ProcessBoundary,
+                _ => return None,
+            };
+            let mut metadata = [None, None, None, None];
+            for (bit, value) in metadata.iter_mut().enumerate() {
+                if tag & (1 << (bit + 3)) != 0 {
+                    *value = Some(string(reader.varint()?)?);
+                }
+            }
+            let [function, module_path, crate_name, crate_version] = metadata;
+
+            Some(WireFrame {
+                file,
+                line,
+                column,
+                kind,
+                function,
+                module_path,
+                crate_name,
+                crate_version,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    reader.0.is_empty().then_some(frames)
+}
+
+impl<E> TracedError<E> {
+    /// Encode this error's call stack using `encode`.
+    pub fn encode_trace(&self) -> Vec<u8> {
+        encode(self.trace())
+    }
+}
+
+/// The index of `string` in `strings`, adding it if it is not included yet.
+fn string_index<'a>(strings: &mut Vec<&'a str>, string: &'a str) -> u64 {
+    let index = match strings.iter().position(|existing| *existing == string) {
+        Some(index) => index,
+        None => {
+            strings.push(string);
+            strings.len() - 1
+        }
+    };
+    index as u64
+}
+
+/// Write `value` as an LEB128 variable-length integer.
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn byte(&mut self) -> Option<u8> {
+        let (&byte, rest) = self.0.split_first()?;
+        self.0 = rest;
+        Some(byte)
+    }
+
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(taken)
+    }
+
+    fn varint(&mut self) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "actix")]
+
+use actix_web::{http::StatusCode, ResponseError};
+use traced_result::{HttpStatus, TracedError};
+
+#[derive(Debug)]
+struct Status(u16);
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "status {}", self.0)
+    }
+}
+
+impl HttpStatus for Status {
+    fn status(&self) -> u16 {
+        self.0
+    }
+}
+
+#[test]
+fn responses_use_error_statuses_only() {
+    let status = |status| {
+        let error = TracedError::new(Status(status));
+        error.trace();
+        error.status_code()
+    };
+    assert_eq!(status(404), StatusCode::NOT_FOUND);
+    assert_eq!(status(503), StatusCode::SERVICE_UNAVAILABLE);
+    for status_code in [200, 302, 42] {
+        assert_eq!(status(status_code), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,10 @@
+use traced_result::{set_capture_enabled, TracedError};
+
+#[test]
+fn errors_created_while_capture_is_disabled_have_empty_call_stacks() {
+    set_capture_enabled(false);
+    let error = TracedError::new("failed");
+    set_capture_enabled(true);
+    assert!(error.trace().is_empty());
+    assert_eq!(TracedError::new("failed").trace().len(), 1);
+}
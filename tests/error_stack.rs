@@ -0,0 +1,47 @@
+#![cfg(feature = "error-stack")]
+
+use std::fmt::Display;
+
+use error_stack::Report;
+use traced_result::{FrameKind, TracedError};
+
+#[derive(Debug)]
+struct TestError;
+
+impl Display for TestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("test error")
+    }
+}
+
+impl std::error::Error for TestError {}
+
+/// The kinds of frames expected for an origin followed by a propagation, which is not recorded with the `origin-only` feature.
+fn origin_and_propagation() -> &'static [FrameKind] {
+    if cfg!(feature = "origin-only") {
+        &[FrameKind::Origin]
+    } else {
+        &[FrameKind::Origin, FrameKind::Propagation]
+    }
+}
+
+#[test]
+fn report_round_trip_keeps_the_origin_first() {
+    let error = TracedError::new(TestError);
+    let origin = error.trace()[0];
+    let report: Report<TestError> = error.into();
+    let error = TracedError::from_report(report);
+
+    let kinds: Vec<_> = error.trace().iter().map(|frame| frame.kind()).collect();
+    assert_eq!(kinds, origin_and_propagation());
+    assert_eq!(error.trace()[0], origin);
+}
+
+#[test]
+fn report_locations_become_frames() {
+    let report = Report::new(TestError);
+    let error = TracedError::from_report(report);
+
+    let kinds: Vec<_> = error.trace().iter().map(|frame| frame.kind()).collect();
+    assert_eq!(kinds, origin_and_propagation());
+}
@@ -0,0 +1,38 @@
+use traced_result::{fingerprint::FingerprintOptions, testing::TraceBuilder};
+
+#[test]
+fn fingerprints_depend_on_the_type_and_origin() {
+    let fingerprint = |file, line, options| {
+        TraceBuilder::new()
+            .frame(file, line, 1)
+            .frame("src/main.rs", 90, 1)
+            .build("failed")
+            .fingerprint_with(options)
+    };
+    let default = FingerprintOptions::new();
+    assert_eq!(
+        fingerprint("src/a.rs", 1, default),
+        fingerprint("src/a.rs", 1, default)
+    );
+    assert_ne!(
+        fingerprint("src/a.rs", 1, default),
+        fingerprint("src/a.rs", 2, default)
+    );
+    assert_ne!(
+        fingerprint("src/a.rs", 1, default),
+        fingerprint("src/b.rs", 1, default)
+    );
+    let positions = default.positions(false);
+    assert_eq!(
+        fingerprint("src/a.rs", 1, positions),
+        fingerprint("src/a.rs", 2, positions)
+    );
+    assert_eq!(
+        fingerprint("src\\a.rs", 1, default),
+        fingerprint("src/a.rs", 1, default)
+    );
+
+    let origin = TraceBuilder::new().frame("src/a.rs", 1, 1).build("failed");
+    let other_type = TraceBuilder::new().frame("src/a.rs", 1, 1).build(1u32);
+    assert_ne!(origin.fingerprint(), other_type.fingerprint());
+}
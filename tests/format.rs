@@ -0,0 +1,29 @@
+use traced_result::{format::FormatOptions, TracedError};
+
+#[test]
+fn message_is_followed_by_a_line_break() {
+    let error = TracedError::new("failed");
+    let output = error.display_with(FormatOptions::stable()).to_string();
+
+    let mut lines = output.lines();
+    assert_eq!(lines.next(), Some("failed"));
+    let position = if cfg!(feature = "no-columns") {
+        "_"
+    } else {
+        "_:_"
+    };
+    assert_eq!(
+        lines.next(),
+        Some(format!("At ({position}) in tests/format.rs").as_str())
+    );
+    assert_eq!(lines.next(), None);
+}
+
+#[test]
+fn output_ends_with_a_line_break() {
+    let error = TracedError::new("failed");
+    let output = error.display_with(FormatOptions::stable()).to_string();
+
+    assert!(output.starts_with("failed\n"));
+    assert!(output.ends_with('\n'));
+}
@@ -0,0 +1,27 @@
+use traced_result::{format::FormatOptions, Config, TracedError};
+
+fn redact_digits(input: &str) -> std::borrow::Cow<'_, str> {
+    input.replace(|c: char| c.is_ascii_digit(), "#").into()
+}
+
+#[test]
+fn merged_branches_use_the_regular_format() {
+    Config::new().scrubber(redact_digits).install().unwrap();
+
+    let first = TracedError::new("token 1234")
+        .with_code("E1")
+        .with_help("retry later");
+    let second = TracedError::new("fallback failed");
+    let merged = first.merge(second);
+
+    let output = merged.to_string();
+    assert!(output.contains("├─ [E1] token 1234\n"));
+    assert!(output.contains("│  help: retry later\n"));
+    assert!(output.contains("└─ fallback failed\n"));
+
+    let report = merged
+        .display_with(FormatOptions::new().scrub(true).color(false))
+        .to_string();
+    assert!(report.contains("token ####"));
+    assert!(!report.contains("1234"));
+}
@@ -0,0 +1,67 @@
+use traced_result::{
+    process::{parse_report, parse_stderr, STDERR_SENTINEL},
+    wire::WireFrameKind,
+    FrameKind, TracedError,
+};
+
+#[test]
+fn process_report_round_trip() {
+    let error = TracedError::new("first line\nsecond line");
+    let origin = error.trace()[0];
+
+    let parsed = parse_report(&error.to_process_report()).unwrap();
+    assert_eq!(parsed.message(), "first line\nsecond line");
+
+    let remote = parsed.remote_trace();
+    assert_eq!(remote.len(), 1);
+    assert_eq!(remote[0].file(), origin.file());
+    assert_eq!(remote[0].line(), origin.line());
+    assert_eq!(*remote[0].kind(), WireFrameKind::Origin);
+
+    let kinds: Vec<_> = parsed.trace().iter().map(|frame| frame.kind()).collect();
+    if cfg!(feature = "origin-only") {
+        assert!(kinds.is_empty());
+    } else {
+        assert_eq!(kinds, [FrameKind::ProcessBoundary]);
+    }
+}
+
+#[test]
+fn process_report_from_stderr() {
+    let error = TracedError::new("failed");
+    let stderr: String = error
+        .to_process_report()
+        .lines()
+        .map(|line| format!("{STDERR_SENTINEL}{line}\nunrelated output\n"))
+        .collect();
+
+    let parsed = parse_stderr(&stderr).unwrap();
+    assert_eq!(parsed.message(), "failed");
+    assert_eq!(parsed.remote_trace().len(), 1);
+}
+
+#[test]
+fn invalid_process_reports_are_rejected() {
+    assert!(parse_report("").is_none());
+    assert!(parse_report("message\tfailed\nframe\tsrc/main.rs\tx\t1\torigin\n").is_none());
+    assert!(parse_report("message\tfailed\nframe\tsrc/main.rs\t1\t1\tunknown\n").is_none());
+}
+
+#[test]
+fn process_reports_without_columns_are_accepted() {
+    let report =
+        "message\tfailed\nframe\tsrc/main.rs\t3\t7\torigin\nframe\tsrc/main.rs\t9\tretry\t2\n";
+    let parsed = parse_report(report).unwrap();
+    let frames: Vec<_> = parsed
+        .remote_trace()
+        .iter()
+        .map(|frame| (frame.line(), frame.column(), frame.kind().clone()))
+        .collect();
+    assert_eq!(
+        frames,
+        [
+            (3, 7, WireFrameKind::Origin),
+            (9, 0, WireFrameKind::Retry { attempt: 2 })
+        ]
+    );
+}
@@ -0,0 +1,103 @@
+use std::{
+    future::Future,
+    pin::pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use traced_result::{
+    reporter::{ErrorReport, Reporter, ReporterBuilder},
+    Severity, TracedError,
+};
+
+fn collecting(builder: ReporterBuilder) -> (Reporter, Arc<Mutex<Vec<ErrorReport>>>) {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let sink = {
+        let reports = reports.clone();
+        move |batch: &[ErrorReport]| reports.lock().unwrap().extend_from_slice(batch)
+    };
+    (builder.sink(sink).spawn().unwrap(), reports)
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        std::thread::yield_now();
+    }
+}
+
+#[test]
+fn flush_writes_queued_reports_in_order() {
+    let (reporter, reports) = collecting(Reporter::builder().batch_size(2));
+    for message in ["first", "second", "third"] {
+        reporter.report(&TracedError::new(message));
+    }
+    reporter.flush();
+
+    let messages: Vec<_> = reports
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|report| report.message().to_owned())
+        .collect();
+    assert_eq!(messages, ["first", "second", "third"]);
+}
+
+#[test]
+fn rate_limit_drops_duplicates_but_not_fatal_errors() {
+    let (reporter, reports) =
+        collecting(Reporter::builder().rate_limit(1, Duration::from_secs(3600)));
+    for _ in 0..3 {
+        reporter.report(&TracedError::new("duplicate"));
+    }
+    reporter.report(&TracedError::new_with_severity("fatal", Severity::Fatal));
+    reporter.report(&TracedError::new_with_severity("fatal", Severity::Fatal));
+    reporter.flush();
+
+    let reports = reports.lock().unwrap();
+    // The first duplicate, followed by a summary of the dropped ones when flushing
+    let suppressed: Vec<_> = reports
+        .iter()
+        .filter(|report| report.message() == "duplicate")
+        .map(ErrorReport::suppressed)
+        .collect();
+    assert_eq!(suppressed, [0, 2]);
+    let fatal = reports.iter().filter(|report| report.message() == "fatal");
+    assert_eq!(fatal.count(), 2);
+}
+
+#[test]
+fn unpolled_async_reports_do_not_use_the_rate_limit() {
+    let (reporter, reports) =
+        collecting(Reporter::builder().rate_limit(1, Duration::from_secs(3600)));
+    let error = TracedError::new("duplicate");
+    drop(reporter.report_async(&error));
+    block_on(reporter.report_async(&error));
+    reporter.flush();
+
+    // Nothing was dropped by the rate limit, so there is no summary
+    let suppressed: Vec<_> = reports
+        .lock()
+        .unwrap()
+        .iter()
+        .map(ErrorReport::suppressed)
+        .collect();
+    assert_eq!(suppressed, [0]);
+}
+
+#[test]
+fn panicking_sinks_do_not_stop_the_reporter() {
+    let (reporter, reports) =
+        collecting(Reporter::builder().sink(|_: &[ErrorReport]| panic!("sink failed")));
+    reporter.report(&TracedError::new("first"));
+    reporter.flush();
+    reporter.report(&TracedError::new("second"));
+    reporter.flush();
+
+    assert_eq!(reports.lock().unwrap().len(), 2);
+}
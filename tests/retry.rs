@@ -0,0 +1,43 @@
+use traced_result::{retry::retry, FrameKind, TracedError, TracedResult};
+
+#[test]
+fn retry_records_every_attempt() {
+    let mut calls = 0;
+    let result: TracedResult<(), &str> = retry(3, || {
+        calls += 1;
+        TracedResult::Err(TracedError::new("failed"))
+    });
+    let TracedResult::Err(error) = result else {
+        panic!("every attempt fails")
+    };
+
+    assert_eq!(calls, 3);
+    let retries: Vec<_> = error
+        .trace()
+        .iter()
+        .filter_map(|frame| match frame.kind() {
+            FrameKind::Retry { attempt } => Some(attempt),
+            _ => None,
+        })
+        .collect();
+    if cfg!(feature = "origin-only") {
+        assert!(retries.is_empty());
+    } else {
+        assert_eq!(retries, [1, 2, 3]);
+    }
+}
+
+#[test]
+fn retry_returns_the_first_success() {
+    let mut calls = 0;
+    let result: TracedResult<u32, &str> = retry(3, || {
+        calls += 1;
+        if calls < 2 {
+            TracedResult::Err(TracedError::new("failed"))
+        } else {
+            TracedResult::Ok(calls)
+        }
+    });
+
+    assert!(matches!(result, TracedResult::Ok(2)));
+}
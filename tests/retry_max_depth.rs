@@ -0,0 +1,14 @@
+use traced_result::{retry::retry, Config, TracedError, TracedResult};
+
+#[test]
+fn retry_history_is_limited_by_max_depth() {
+    Config::new().max_depth(4).install().unwrap();
+
+    let result: TracedResult<(), &str> =
+        retry(100, || TracedResult::Err(TracedError::new("failed")));
+    let TracedResult::Err(error) = result else {
+        panic!("every attempt fails")
+    };
+
+    assert!(error.trace().len() <= 4);
+}
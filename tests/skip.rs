@@ -0,0 +1,25 @@
+#![cfg(feature = "macros")]
+
+use std::num::ParseIntError;
+
+use traced_result::{FrameKind, ParseTraced, TracedResult};
+
+#[traced_result::traced::skip]
+fn forward(input: &str) -> TracedResult<u32, ParseIntError> {
+    let number = input.parse_traced::<u32>()?;
+    TracedResult::Ok(number)
+}
+
+#[test]
+fn errors_created_inside_skipped_functions_start_at_the_caller() {
+    let line = line!() + 1;
+    let TracedResult::Err(error) = forward("x") else {
+        panic!("\"x\" is not a number")
+    };
+    let frames: Vec<_> = error
+        .trace()
+        .iter()
+        .map(|frame| (frame.line(), frame.kind()))
+        .collect();
+    assert_eq!(frames, [(line, FrameKind::Origin)]);
+}
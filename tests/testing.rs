@@ -0,0 +1,77 @@
+use traced_result::{
+    testing::{FrameDiff, TraceBuilder, TraceDiff},
+    Frame,
+};
+
+fn trace(frames: &[(&'static str, u32)]) -> Vec<Frame> {
+    let builder = frames
+        .iter()
+        .fold(TraceBuilder::new(), |builder, &(file, line)| {
+            builder.frame(file, line, 1)
+        });
+    builder.build(()).trace().to_vec()
+}
+
+/// How a frame built by `trace` is displayed.
+fn location(file: &str, line: u32) -> String {
+    if cfg!(feature = "no-columns") {
+        format!("{file}:{line}")
+    } else {
+        format!("{file}:{line}:1")
+    }
+}
+
+#[test]
+fn identical_traces_have_no_changes() {
+    let frames = trace(&[("src/a.rs", 1), ("src/b.rs", 2)]);
+    let diff = TraceDiff::new(&frames, &frames);
+    assert!(!diff.has_changes());
+    assert_eq!(
+        diff.to_string(),
+        format!(
+            "  {}\n  {}\n",
+            location("src/a.rs", 1),
+            location("src/b.rs", 2)
+        )
+    );
+}
+
+#[test]
+fn differing_frames_are_added_removed_or_changed() {
+    let expected = trace(&[
+        ("src/a.rs", 1),
+        ("src/b.rs", 2),
+        ("src/c.rs", 3),
+        ("src/e.rs", 6),
+    ]);
+    let actual = trace(&[
+        ("src/a.rs", 1),
+        ("src/b.rs", 5),
+        ("src/c.rs", 3),
+        ("src/d.rs", 4),
+    ]);
+    let diff = TraceDiff::new(&expected, &actual);
+    assert!(diff.has_changes());
+    assert!(matches!(
+        diff.entries(),
+        [
+            FrameDiff::Same(_),
+            FrameDiff::Changed { .. },
+            FrameDiff::Same(_),
+            FrameDiff::Removed(_),
+            FrameDiff::Added(_),
+        ]
+    ));
+    assert_eq!(
+        diff.to_string(),
+        format!(
+            "  {}\n~ {} -> {}\n  {}\n- {}\n+ {}\n",
+            location("src/a.rs", 1),
+            location("src/b.rs", 2),
+            location("src/b.rs", 5),
+            location("src/c.rs", 3),
+            location("src/e.rs", 6),
+            location("src/d.rs", 4)
+        )
+    );
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "tonic")]
+
+use tonic::Status;
+use traced_result::{
+    tonic::{continue_trace, RemoteStatus},
+    HttpStatus, TracedError,
+};
+
+#[derive(Debug)]
+struct NotFound;
+
+impl std::fmt::Display for NotFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("not found")
+    }
+}
+
+impl HttpStatus for NotFound {
+    fn status(&self) -> u16 {
+        404
+    }
+}
+
+#[test]
+fn statuses_carry_the_call_stack() {
+    let error = TracedError::new(NotFound);
+    let origin = error.trace()[0];
+    let status = Status::from(error);
+    assert_eq!(status.code(), tonic::Code::NotFound);
+
+    let error = continue_trace(status);
+    let remote = error.remote_trace();
+    assert_eq!(remote.len(), 1);
+    assert_eq!(
+        (remote[0].file.as_str(), remote[0].line),
+        (origin.file(), origin.line())
+    );
+    assert_eq!(remote[0].column, origin.column());
+    assert_eq!(remote[0].to_string(), origin.to_string());
+}
+
+#[test]
+fn frames_without_columns_are_accepted() {
+    let mut status = Status::not_found("not found");
+    status.metadata_mut().insert_bin(
+        traced_result::tonic::FRAMES_METADATA_KEY,
+        tonic::metadata::MetadataValue::from_bytes(b"C:\\src\\main.rs:3:7\nC:\\src\\main.rs:9"),
+    );
+    let status = RemoteStatus::from(status);
+    let frames: Vec<_> = status
+        .remote_trace()
+        .iter()
+        .map(|frame| (frame.file.as_str(), frame.line, frame.column))
+        .collect();
+    assert_eq!(
+        frames,
+        [("C:\\src\\main.rs", 3, 7), ("C:\\src\\main.rs", 9, 0)]
+    );
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "tracing-subscriber")]
+
+use std::sync::{Arc, Mutex};
+
+use traced_result::{
+    reporter::{ErrorReport, Reporter},
+    tracing_subscriber::TracedErrorLayer,
+    TracedError,
+};
+use tracing_subscriber::layer::SubscriberExt;
+
+fn collect_reports(emit: impl FnOnce()) -> Vec<ErrorReport> {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let sink = {
+        let reports = reports.clone();
+        move |batch: &[ErrorReport]| reports.lock().unwrap().extend_from_slice(batch)
+    };
+    let reporter = Arc::new(Reporter::builder().sink(sink).spawn().unwrap());
+    let subscriber = tracing_subscriber::registry().with(TracedErrorLayer::new(reporter.clone()));
+    tracing::subscriber::with_default(subscriber, emit);
+    reporter.flush();
+    let reports = reports.lock().unwrap().clone();
+    reports
+}
+
+#[test]
+fn layer_keeps_error_attachments() {
+    let error = TracedError::new("failed")
+        .with_code("E1")
+        .tag("tenant", "a");
+    let reports = collect_reports(|| {
+        tracing::error!(
+            traced_error = &ErrorReport::new(&error) as &(dyn std::error::Error + 'static),
+            "request failed"
+        );
+    });
+
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].message(), "failed");
+    assert_eq!(reports[0].code(), Some("E1"));
+    let tags: Vec<_> = reports[0].tags().collect();
+    assert_eq!(tags, [("tenant", "a"), ("target", "tracing_subscriber")]);
+}
+
+#[test]
+fn layer_parses_process_reports() {
+    let error = TracedError::new("failed");
+    let reports = collect_reports(|| {
+        tracing::error!(traced_error = %error.to_process_report(), "request failed");
+        tracing::error!(traced_error = "not a report", "unrelated");
+    });
+
+    assert_eq!(reports.len(), 1);
+    assert_eq!(reports[0].message(), "failed");
+    assert_eq!(reports[0].frames().len(), 1);
+    assert_eq!(reports[0].frames()[0].file(), error.trace()[0].file());
+}
@@ -0,0 +1,138 @@
+#![feature(try_blocks, try_blocks_heterogeneous, yeet_expr, iterator_try_collect)]
+
+use std::num::ParseIntError;
+
+use traced_result::{FrameKind, IntoTraced, NoneError, TracedError, TracedOption, TracedResult};
+
+#[derive(Debug, PartialEq)]
+enum InputError {
+    Parse,
+    Missing,
+}
+
+impl From<ParseIntError> for InputError {
+    fn from(_: ParseIntError) -> Self {
+        InputError::Parse
+    }
+}
+
+impl From<NoneError> for InputError {
+    fn from(_: NoneError) -> Self {
+        InputError::Missing
+    }
+}
+
+fn parse(input: &str) -> TracedResult<u32, ParseIntError> {
+    input.parse::<u32>().traced()
+}
+
+fn unwrap_err<T, E>(result: TracedResult<T, E>) -> TracedError<E> {
+    match result {
+        TracedResult::Ok(_) => panic!("expected an error"),
+        TracedResult::Err(error) => error,
+    }
+}
+
+/// The kinds of the frames in the error's call stack, checked against the `expected` ones the default configuration records.
+fn assert_kinds<E>(error: &TracedError<E>, expected: &[FrameKind]) {
+    let kinds: Vec<_> = error.trace().iter().map(|frame| frame.kind()).collect();
+    if cfg!(feature = "origin-only") {
+        assert_eq!(kinds, [FrameKind::Origin]);
+    } else {
+        assert_eq!(kinds, expected);
+    }
+}
+
+fn conversion<E, U>() -> FrameKind {
+    FrameKind::Conversion {
+        from: std::any::type_name::<E>(),
+        to: std::any::type_name::<U>(),
+    }
+}
+
+#[test]
+fn try_block_type_is_inferred_from_traced_results() {
+    let total = try { parse("1")? + parse("x")? };
+    let error = unwrap_err(total);
+    assert_kinds(&error, &[FrameKind::Origin, FrameKind::Propagation]);
+
+    let total = try { parse("1")? + parse("2")? };
+    assert!(matches!(total, TracedResult::Ok(3)));
+}
+
+#[test]
+fn result_residuals_start_a_call_stack() {
+    let total = try bikeshed TracedResult<u32, InputError> { parse("1")? + "x".parse::<u32>()? };
+    let error = unwrap_err(total);
+    assert_eq!(*error, InputError::Parse);
+    assert_eq!(error.trace()[0].file(), file!());
+    assert_kinds(&error, &[FrameKind::Origin]);
+}
+
+#[test]
+fn option_residuals_start_a_call_stack() {
+    let missing: Option<u32> = None;
+    let total = try bikeshed TracedResult<u32, InputError> { parse("1")? + missing? };
+    let error = unwrap_err(total);
+    assert_eq!(*error, InputError::Missing);
+    assert_kinds(&error, &[FrameKind::Origin]);
+}
+
+#[test]
+fn traced_result_residuals_record_conversions() {
+    let total = try bikeshed TracedResult<u32, InputError> { parse("x")? };
+    let error = unwrap_err(total);
+    assert_eq!(*error, InputError::Parse);
+    assert_kinds(
+        &error,
+        &[
+            FrameKind::Origin,
+            FrameKind::Propagation,
+            conversion::<ParseIntError, InputError>(),
+        ],
+    );
+}
+
+#[test]
+fn traced_option_residuals_keep_their_call_stack() {
+    let total = try bikeshed TracedResult<u32, InputError> {
+        let value: TracedOption<u32> = None.into();
+        value?
+    };
+    let error = unwrap_err(total);
+    assert_eq!(*error, InputError::Missing);
+    assert_kinds(
+        &error,
+        &[
+            FrameKind::Origin,
+            FrameKind::Propagation,
+            conversion::<NoneError, InputError>(),
+        ],
+    );
+}
+
+#[test]
+fn yeet_starts_a_call_stack() {
+    fn fail() -> TracedResult<u32, InputError> {
+        do yeet "x".parse::<u32>().unwrap_err();
+    }
+
+    let error = unwrap_err(fail());
+    assert_eq!(*error, InputError::Parse);
+    assert_eq!(error.trace()[0].file(), file!());
+    assert_kinds(&error, &[FrameKind::Origin]);
+}
+
+#[test]
+fn try_collect_produces_a_traced_result() {
+    let numbers: TracedResult<Vec<u32>, ParseIntError> =
+        ["1", "2", "3"].into_iter().map(parse).try_collect();
+    assert!(matches!(numbers, TracedResult::Ok(ref numbers) if numbers == &[1, 2, 3]));
+
+    let numbers = ["1", "x", "3"]
+        .into_iter()
+        .map(parse)
+        .try_collect::<Vec<_>>();
+    let error = unwrap_err(numbers);
+    assert_kinds(&error, &[FrameKind::Origin, FrameKind::Propagation]);
+}
@@ -0,0 +1,60 @@
+use traced_result::{
+    testing::TraceBuilder,
+    wire::{decode, encode, encode_with, WireFrame, WireOptions},
+    FrameKind,
+};
+
+fn frames() -> Vec<traced_result::Frame> {
+    let error = TraceBuilder::new()
+        .frame("src/parse.rs", 120, 9)
+        .frame("src/parse.rs", 80, 17)
+        .frame_of_kind(
+            "src/main.rs",
+            12,
+            5,
+            FrameKind::Conversion {
+                from: "core::num::error::ParseIntError",
+                to: "app::Error",
+            },
+        )
+        .frame_of_kind("src/main.rs", 30, 1, FrameKind::Retry { attempt: 2 })
+        .frame_of_kind("src/main.rs", 4, 1, FrameKind::ProcessBoundary)
+        .build(());
+    error.trace().to_vec()
+}
+
+#[test]
+fn decoding_restores_encoded_frames() {
+    let frames = frames();
+    let expected: Vec<_> = frames.iter().map(WireFrame::from).collect();
+    assert_eq!(decode(&encode(&frames)).unwrap(), expected);
+    let absolute = encode_with(&frames, WireOptions::new().delta(false));
+    assert_eq!(decode(&absolute).unwrap(), expected);
+}
+
+#[test]
+fn invalid_encodings_are_rejected() {
+    let encoded = encode(&frames());
+    for len in 0..encoded.len() {
+        assert_eq!(decode(&encoded[..len]), None);
+    }
+    let mut trailing = encoded.clone();
+    trailing.push(0);
+    assert_eq!(decode(&trailing), None);
+    let mut version = encoded;
+    version[0] += 1;
+    assert_eq!(decode(&version), None);
+}
+
+#[test]
+fn line_overflows_are_rejected() {
+    // Version, delta encoded lines, the string "a", then a frame moving 2^32 lines down from line 0
+    let mut bytes = vec![1, 1, 1, 1, b'a', 1, 0];
+    let mut zigzag = 1u64 << 33;
+    while zigzag >= 0x80 {
+        bytes.push(zigzag as u8 | 0x80);
+        zigzag >>= 7;
+    }
+    bytes.extend([zigzag as u8, 0, 0]);
+    assert_eq!(decode(&bytes), None);
+}